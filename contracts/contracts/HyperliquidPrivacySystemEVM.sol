@@ -27,6 +27,12 @@ contract HyperliquidPrivacySystemEVM {
     
     ISP1Verifier public sp1Verifier;
     address public complianceAuthority;
+    bytes32 public issuerSetRoot; // Root of the trusted certificate issuers
+    bytes32 public revocationRoot; // Root of the authority's revoked certificate hashes
+    bytes32 public policyHash; // Predicate hash of the policy depositors must satisfy
+    
+    // Compliance proofs are evaluated at a prover-chosen time, which must be recent
+    uint256 public constant MAX_COMPLIANCE_PROOF_AGE = 1 hours;
     address public owner;
     address public dexExtension;
     
@@ -54,10 +60,13 @@ contract HyperliquidPrivacySystemEVM {
     IHyperCoreWrite constant HYPERCORE_WRITE = IHyperCoreWrite(0x3333333333333333333333333333333333333333); // Write system contract
 
     // Events
-    event PrivateDeposit(bytes32 indexed commitment, uint256 timestamp);
+    event PrivateDeposit(bytes32 indexed commitment, uint64 token, uint256 amount, bytes32 amountBlinding, uint256 timestamp);
     event PrivateWithdraw(bytes32 indexed nullifier, uint256 timestamp);
     event PrivateSpotTrade(address indexed user, uint32 coin, bool isBuy, uint64 sz, uint256 timestamp);
     event TokenAdded(uint64 indexed tokenId, address tokenAddress);
+    event IssuerSetRootUpdated(bytes32 newRoot);
+    event RevocationRootUpdated(bytes32 newRoot);
+    event PolicyUpdated(bytes32 policyHash);
     
     // ZK proof structures
     struct BalanceProofPublicValues {
@@ -67,11 +76,17 @@ contract HyperliquidPrivacySystemEVM {
         uint64 assetId;
     }
     
+    // Certificate mode layout of the compliance circuit's public values
     struct ComplianceProofPublicValues {
+        uint8 mode;
         bytes32 commitment;
-        address complianceAuthority;
+        bytes32 issuerSetRoot;
         uint256 validUntil;
+        uint64 timestamp;
         bytes32 certificateHash;
+        bytes32 revocationRoot;
+        bytes32 predicateHash;
+        bool predicatesSatisfied;
     }
 
     struct TradeProofPublicValues {
@@ -108,6 +123,27 @@ contract HyperliquidPrivacySystemEVM {
         emit TokenAdded(tokenId, tokenAddress);
     }
     
+    /// @notice Update the trusted issuer set root
+    function updateIssuerSetRoot(bytes32 newRoot) external {
+        require(msg.sender == complianceAuthority, "Only compliance authority");
+        issuerSetRoot = newRoot;
+        emit IssuerSetRootUpdated(newRoot);
+    }
+    
+    /// @notice Update the revocation list root
+    function updateRevocationRoot(bytes32 newRoot) external {
+        require(msg.sender == complianceAuthority, "Only compliance authority");
+        revocationRoot = newRoot;
+        emit RevocationRootUpdated(newRoot);
+    }
+    
+    /// @notice Update the deposit policy's predicate hash
+    function updatePolicy(bytes32 newPolicyHash) external {
+        require(msg.sender == complianceAuthority, "Only compliance authority");
+        policyHash = newPolicyHash;
+        emit PolicyUpdated(newPolicyHash);
+    }
+    
     /// @notice Prepare deposit (EVM-only)
     function prepareDeposit(uint64 token, uint256 amount) external {
         require(tokenAddresses[token] != address(0) || token == 0, "Token not supported");
@@ -140,19 +176,28 @@ contract HyperliquidPrivacySystemEVM {
     }
     
     /// @notice Complete deposit with ZK proof
+    /// @dev The deposited amount is public, so its blinding is too; notes created
+    /// by trades and transfers keep their amount blindings private
     function completeDeposit(
         bytes32 commitment,
+        bytes32 amountBlinding,
         bytes calldata complianceProof,
         bytes calldata publicValues
     ) external virtual {
         require(_canCompleteDeposit(msg.sender), "No valid pending deposit");
         require(!commitments[commitment], "Commitment already used");
         
-        // Verify compliance proof
+        // Verify compliance proof; only certificate mode (mode 0) is accepted
         ComplianceProofPublicValues memory compliance = abi.decode(publicValues, (ComplianceProofPublicValues));
+        require(compliance.mode == 0, "Not a certificate proof");
         require(compliance.commitment == commitment, "Commitment mismatch");
-        require(compliance.complianceAuthority == complianceAuthority, "Invalid authority");
+        require(compliance.issuerSetRoot == issuerSetRoot, "Untrusted issuer set");
         require(compliance.validUntil > block.timestamp, "Certificate expired");
+        require(compliance.revocationRoot == revocationRoot, "Outdated revocation root");
+        require(compliance.predicateHash == policyHash, "Wrong policy");
+        require(compliance.predicatesSatisfied, "Policy not satisfied");
+        require(compliance.timestamp <= block.timestamp, "Proof timestamp in the future");
+        require(block.timestamp - compliance.timestamp <= MAX_COMPLIANCE_PROOF_AGE, "Compliance proof too old");
         
         sp1Verifier.verifyProof(
             InnocenceVerificationKeys.COMPLIANCE_VKEY,
@@ -160,14 +205,17 @@ contract HyperliquidPrivacySystemEVM {
             complianceProof
         );
         
-        // Add commitment to merkle tree
+        // Add the deposit's note leaf to the merkle tree; this is the leaf the
+        // balance, withdraw, trade and transfer circuits prove membership of
+        PendingDeposit memory deposit = pendingDeposits[msg.sender];
         commitments[commitment] = true;
-        commitmentMerkleTree.push(commitment);
+        bytes32 amountCommitment = keccak256(abi.encodePacked(deposit.amount, amountBlinding));
+        commitmentMerkleTree.push(keccak256(abi.encodePacked(commitment, deposit.token, amountCommitment)));
         
         // Mark deposit as completed
         pendingDeposits[msg.sender].completed = true;
         
-        emit PrivateDeposit(commitment, block.timestamp);
+        emit PrivateDeposit(commitment, deposit.token, deposit.amount, amountBlinding, block.timestamp);
     }
     
     /// @notice Withdraw tokens with ZK proof (Pure EVM)
@@ -10,6 +10,12 @@ contract HyperliquidPrivacySystemEVMWithRecovery {
     
     ISP1Verifier public sp1Verifier;
     address public complianceAuthority;
+    bytes32 public issuerSetRoot; // Root of the trusted certificate issuers
+    bytes32 public revocationRoot; // Root of the authority's revoked certificate hashes
+    bytes32 public policyHash; // Predicate hash of the policy depositors must satisfy
+    
+    // Compliance proofs are evaluated at a prover-chosen time, which must be recent
+    uint256 public constant MAX_COMPLIANCE_PROOF_AGE = 1 hours;
     
     // EVM token mappings (token ID → ERC20 address)
     mapping(uint64 => address) public tokenAddresses;
@@ -30,10 +36,13 @@ contract HyperliquidPrivacySystemEVMWithRecovery {
     mapping(address => PendingDeposit) public pendingDeposits;
     
     // Events
-    event PrivateDeposit(bytes32 indexed commitment, uint256 timestamp);
+    event PrivateDeposit(bytes32 indexed commitment, uint64 token, uint256 amount, bytes32 amountBlinding, uint256 timestamp);
     event PrivateWithdraw(bytes32 indexed nullifier, uint256 timestamp);
     event EmergencyWithdraw(address indexed recipient, uint256 amount, string reason);
     event TokenAdded(uint64 indexed tokenId, address tokenAddress);
+    event IssuerSetRootUpdated(bytes32 newRoot);
+    event RevocationRootUpdated(bytes32 newRoot);
+    event PolicyUpdated(bytes32 policyHash);
     
     // ZK proof structures
     struct BalanceProofPublicValues {
@@ -43,11 +52,17 @@ contract HyperliquidPrivacySystemEVMWithRecovery {
         uint64 assetId;
     }
     
+    // Certificate mode layout of the compliance circuit's public values
     struct ComplianceProofPublicValues {
+        uint8 mode;
         bytes32 commitment;
-        address complianceAuthority;
+        bytes32 issuerSetRoot;
         uint256 validUntil;
+        uint64 timestamp;
         bytes32 certificateHash;
+        bytes32 revocationRoot;
+        bytes32 predicateHash;
+        bool predicatesSatisfied;
     }
     
     constructor(
@@ -98,6 +113,27 @@ contract HyperliquidPrivacySystemEVMWithRecovery {
         emit TokenAdded(tokenId, tokenAddress);
     }
     
+    /// @notice Update the trusted issuer set root
+    function updateIssuerSetRoot(bytes32 newRoot) external {
+        require(msg.sender == complianceAuthority, "Only compliance authority");
+        issuerSetRoot = newRoot;
+        emit IssuerSetRootUpdated(newRoot);
+    }
+    
+    /// @notice Update the revocation list root
+    function updateRevocationRoot(bytes32 newRoot) external {
+        require(msg.sender == complianceAuthority, "Only compliance authority");
+        revocationRoot = newRoot;
+        emit RevocationRootUpdated(newRoot);
+    }
+    
+    /// @notice Update the deposit policy's predicate hash
+    function updatePolicy(bytes32 newPolicyHash) external {
+        require(msg.sender == complianceAuthority, "Only compliance authority");
+        policyHash = newPolicyHash;
+        emit PolicyUpdated(newPolicyHash);
+    }
+    
     /// @notice Prepare deposit (EVM-only)
     function prepareDeposit(uint64 token, uint256 amount) external {
         require(tokenAddresses[token] != address(0) || token == 0, "Token not supported");
@@ -130,19 +166,28 @@ contract HyperliquidPrivacySystemEVMWithRecovery {
     }
     
     /// @notice Complete deposit with ZK proof
+    /// @dev The deposited amount is public, so its blinding is too; notes created
+    /// by trades and transfers keep their amount blindings private
     function completeDeposit(
         bytes32 commitment,
+        bytes32 amountBlinding,
         bytes calldata complianceProof,
         bytes calldata publicValues
     ) external {
         require(_canCompleteDeposit(msg.sender), "No valid pending deposit");
         require(!commitments[commitment], "Commitment already used");
         
-        // Verify compliance proof
+        // Verify compliance proof; only certificate mode (mode 0) is accepted
         ComplianceProofPublicValues memory compliance = abi.decode(publicValues, (ComplianceProofPublicValues));
+        require(compliance.mode == 0, "Not a certificate proof");
         require(compliance.commitment == commitment, "Commitment mismatch");
-        require(compliance.complianceAuthority == complianceAuthority, "Invalid authority");
+        require(compliance.issuerSetRoot == issuerSetRoot, "Untrusted issuer set");
         require(compliance.validUntil > block.timestamp, "Certificate expired");
+        require(compliance.revocationRoot == revocationRoot, "Outdated revocation root");
+        require(compliance.predicateHash == policyHash, "Wrong policy");
+        require(compliance.predicatesSatisfied, "Policy not satisfied");
+        require(compliance.timestamp <= block.timestamp, "Proof timestamp in the future");
+        require(block.timestamp - compliance.timestamp <= MAX_COMPLIANCE_PROOF_AGE, "Compliance proof too old");
         
         sp1Verifier.verifyProof(
             InnocenceVerificationKeys.COMPLIANCE_VKEY,
@@ -150,14 +195,17 @@ contract HyperliquidPrivacySystemEVMWithRecovery {
             complianceProof
         );
         
-        // Add commitment to merkle tree
+        // Add the deposit's note leaf to the merkle tree; this is the leaf the
+        // balance, withdraw, trade and transfer circuits prove membership of
+        PendingDeposit memory deposit = pendingDeposits[msg.sender];
         commitments[commitment] = true;
-        commitmentMerkleTree.push(commitment);
+        bytes32 amountCommitment = keccak256(abi.encodePacked(deposit.amount, amountBlinding));
+        commitmentMerkleTree.push(keccak256(abi.encodePacked(commitment, deposit.token, amountCommitment)));
         
         // Mark deposit as completed
         pendingDeposits[msg.sender].completed = true;
         
-        emit PrivateDeposit(commitment, block.timestamp);
+        emit PrivateDeposit(commitment, deposit.token, deposit.amount, amountBlinding, block.timestamp);
     }
     
     /// @notice Withdraw tokens with ZK proof (Pure EVM)
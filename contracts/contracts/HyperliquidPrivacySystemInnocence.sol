@@ -21,6 +21,15 @@ contract HyperliquidPrivacySystemInnocence {
     mapping(address => bool) public hasProvenInnocence; // Track who has proven they're not sanctioned
     mapping(address => uint256) public innocenceProofExpiry; // When their proof expires
     
+    // Deposit tree of note leaves, matching innocence_circuits_lib::tree::IncrementalMerkleTree
+    uint32 public constant TREE_DEPTH = 20;
+    uint32 public constant ROOT_HISTORY_SIZE = 30;
+    bytes32[TREE_DEPTH + 1] public zeros; // zeros[i] is the root of an empty subtree of height i
+    bytes32[TREE_DEPTH] public filledSubtrees;
+    bytes32[ROOT_HISTORY_SIZE] public roots;
+    uint32 public currentRootIndex;
+    uint32 public nextIndex;
+    
     // Deposit tracking
     struct PendingDeposit {
         uint64 token;
@@ -32,22 +41,28 @@ contract HyperliquidPrivacySystemInnocence {
     
     // Events
     event InnocenceProven(address indexed depositor, uint256 expiryTime);
-    event PrivateDeposit(bytes32 indexed commitment, address indexed depositor, uint256 timestamp);
-    event PrivateWithdraw(bytes32 indexed nullifier, address recipient, uint256 amount, uint256 timestamp);
+    event PrivateDeposit(bytes32 indexed commitment, address indexed depositor, uint64 token, uint256 amount, bytes32 amountBlinding, uint256 timestamp);
+    event PrivateWithdraw(bytes32 indexed nullifierHash, address recipient, address relayer, uint256 fee, uint256 amount, uint256 timestamp);
     event SanctionsRootUpdated(bytes32 newRoot);
     
     // ZK proof structures
+    // mode 0 proves non-membership in the sanctions tree under listRoot,
+    // mode 1 membership in an allowlist tree under listRoot
     struct InnocenceProofPublicValues {
+        uint8 mode;
         address depositor;
-        bytes32 sanctionsRoot;
+        bytes32 listRoot;
         uint256 timestamp;
         bool isInnocent;
     }
     
-    struct BalanceProofPublicValues {
-        bytes32 commitment;
+    struct WithdrawProofPublicValues {
         bytes32 merkleRoot;
-        uint256 minBalance;
+        bytes32 nullifierHash;
+        address recipient;
+        address relayer;
+        uint256 fee;
+        uint256 amount;
         uint64 assetId;
     }
     
@@ -60,6 +75,13 @@ contract HyperliquidPrivacySystemInnocence {
         
         // Initialize with native ETH (token ID 0)
         tokenAddresses[0] = address(0);
+        
+        // Empty tree: zero leaves and the zero-subtree hashes above them
+        for (uint32 i = 0; i < TREE_DEPTH; i++) {
+            zeros[i + 1] = keccak256(abi.encodePacked(zeros[i], zeros[i]));
+            filledSubtrees[i] = zeros[i];
+        }
+        roots[0] = zeros[TREE_DEPTH];
     }
     
     /// @notice Update the sanctions list merkle root
@@ -78,9 +100,10 @@ contract HyperliquidPrivacySystemInnocence {
         // Decode public values
         InnocenceProofPublicValues memory values = abi.decode(publicValues, (InnocenceProofPublicValues));
         
-        // Verify the proof matches the caller
+        // Verify the proof matches the caller; only sanctions mode is accepted
+        require(values.mode == 0, "Not a sanctions proof");
         require(values.depositor == msg.sender, "Proof not for caller");
-        require(values.sanctionsRoot == currentSanctionsRoot, "Outdated sanctions root");
+        require(values.listRoot == currentSanctionsRoot, "Outdated sanctions root");
         require(values.isInnocent, "Address is sanctioned");
         
         // Verify the ZK proof
@@ -124,7 +147,8 @@ contract HyperliquidPrivacySystemInnocence {
     }
     
     /// @notice Complete deposit with commitment (step 2)
-    function completeDeposit(bytes32 commitment) external {
+    /// @dev amountBlinding is emitted so indexers can rebuild the note leaf
+    function completeDeposit(bytes32 commitment, bytes32 amountBlinding) external {
         PendingDeposit memory deposit = pendingDeposits[msg.sender];
         require(deposit.timestamp > 0, "No pending deposit");
         require(!deposit.completed, "Deposit already completed");
@@ -136,47 +160,96 @@ contract HyperliquidPrivacySystemInnocence {
         // Mark deposit as completed
         pendingDeposits[msg.sender].completed = true;
         
-        emit PrivateDeposit(commitment, msg.sender, block.timestamp);
+        // Insert the note leaf: hash(commitment, token, hash(amount, amountBlinding))
+        _insert(keccak256(abi.encodePacked(
+            commitment,
+            deposit.token,
+            keccak256(abi.encodePacked(deposit.amount, amountBlinding))
+        )));
+        
+        emit PrivateDeposit(commitment, msg.sender, deposit.token, deposit.amount, amountBlinding, block.timestamp);
     }
     
-    /// @notice Withdraw with balance proof
+    /// @notice Withdraw a deposited note with a withdraw proof
+    /// @dev The nullifier hash, recipient, relayer and fee all come from the proof's
+    ///      public values, so a submitted proof cannot be redirected or replayed
     function withdraw(
-        bytes32 nullifier,
-        address recipient,
-        uint64 token,
-        uint256 amount,
-        bytes calldata balanceProof,
+        bytes calldata withdrawProof,
         bytes calldata publicValues
     ) external {
-        require(!nullifiers[nullifier], "Nullifier already used");
-        
-        // Decode and verify balance proof
-        BalanceProofPublicValues memory balance = abi.decode(publicValues, (BalanceProofPublicValues));
-        require(balance.minBalance >= amount, "Insufficient proven balance");
-        require(balance.assetId == token, "Asset mismatch");
-        
-        // For simplicity, we're not implementing merkle tree in this version
-        // In production, you'd verify balance.merkleRoot against stored commitments
+        WithdrawProofPublicValues memory values = abi.decode(publicValues, (WithdrawProofPublicValues));
+        require(!nullifiers[values.nullifierHash], "Nullifier already used");
+        require(isKnownRoot(values.merkleRoot), "Unknown merkle root");
+        require(values.fee <= values.amount, "Fee exceeds amount");
+        require(values.assetId == 0 || tokenAddresses[values.assetId] != address(0), "Token not supported");
         
         // Verify the ZK proof
         sp1Verifier.verifyProof(
-            InnocenceVerificationKeys.BALANCE_VKEY,
+            InnocenceVerificationKeys.WITHDRAW_VKEY,
             publicValues,
-            balanceProof
+            withdrawProof
         );
         
-        nullifiers[nullifier] = true;
+        nullifiers[values.nullifierHash] = true;
+        
+        // Transfer tokens, paying the relayer's fee out of the amount
+        _transfer(values.assetId, values.recipient, values.amount - values.fee);
+        if (values.fee > 0) {
+            _transfer(values.assetId, values.relayer, values.fee);
+        }
         
-        // Transfer tokens
+        emit PrivateWithdraw(values.nullifierHash, values.recipient, values.relayer, values.fee, values.amount, block.timestamp);
+    }
+    
+    /// @notice Current deposit tree root
+    function getMerkleRoot() public view returns (bytes32) {
+        return roots[currentRootIndex];
+    }
+    
+    /// @notice Whether root is the current deposit tree root or one of the last ROOT_HISTORY_SIZE
+    function isKnownRoot(bytes32 root) public view returns (bool) {
+        // Unused history slots are zero; the zero root is never valid
+        if (root == bytes32(0)) {
+            return false;
+        }
+        for (uint32 i = 0; i < ROOT_HISTORY_SIZE; i++) {
+            if (roots[i] == root) {
+                return true;
+            }
+        }
+        return false;
+    }
+    
+    /// @dev Append a leaf, rehashing its path to the root
+    function _insert(bytes32 leaf) internal {
+        uint32 index = nextIndex;
+        require(index < uint32(1) << TREE_DEPTH, "Merkle tree is full");
+        
+        bytes32 current = leaf;
+        for (uint32 i = 0; i < TREE_DEPTH; i++) {
+            // An odd position's left sibling is filled; an even one pairs with a zero subtree
+            if (index % 2 == 0) {
+                filledSubtrees[i] = current;
+                current = keccak256(abi.encodePacked(current, zeros[i]));
+            } else {
+                current = keccak256(abi.encodePacked(filledSubtrees[i], current));
+            }
+            index /= 2;
+        }
+        
+        currentRootIndex = (currentRootIndex + 1) % ROOT_HISTORY_SIZE;
+        roots[currentRootIndex] = current;
+        nextIndex += 1;
+    }
+    
+    function _transfer(uint64 token, address to, uint256 amount) internal {
         if (token == 0) {
             // Native ETH
-            payable(recipient).transfer(amount);
+            payable(to).transfer(amount);
         } else {
             // ERC20
-            IERC20(tokenAddresses[token]).transfer(recipient, amount);
+            IERC20(tokenAddresses[token]).transfer(to, amount);
         }
-        
-        emit PrivateWithdraw(nullifier, recipient, amount, block.timestamp);
     }
     
     /// @notice Check if an address has valid innocence proof
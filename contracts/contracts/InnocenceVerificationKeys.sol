@@ -7,4 +7,5 @@ library InnocenceVerificationKeys {
     bytes32 public constant COMPLIANCE_VKEY = 0x00ed1611619b8f2866de7be17d81a9d42a869b4c5959629708f125ae34a2f9ee; // DEPRECATED
     bytes32 public constant TRADE_VKEY = 0x008484840d42565b9589f5c37253789d3731407964b1537fb5cf9d6064226b1c;
     bytes32 public constant INNOCENCE_VKEY = 0x0000000000000000000000000000000000000000000000000000000000000005; // TODO: Generate actual vkey
+    bytes32 public constant WITHDRAW_VKEY = 0x0000000000000000000000000000000000000000000000000000000000000006; // TODO: Generate actual vkey
 }
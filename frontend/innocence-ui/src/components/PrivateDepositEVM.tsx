@@ -121,6 +121,8 @@ export function PrivateDepositEVM({ privacySystem, userAddress }: PrivateDeposit
       setPendingDepositData({
         secret,
         nullifier,
+        // Blinds the amount in the deposit's tree leaf; needed to spend the note
+        amountBlinding: proofService.generateSecret(),
         commitment,
         amount: amount,
         amountWei: amountWei.toString(),
@@ -296,6 +298,7 @@ export function PrivateDepositEVM({ privacySystem, userAddress }: PrivateDeposit
           // Use EVM completeDeposit function (ETH should already be sent)
           const tx = await privacySystem.completeDeposit({
             commitment,
+            amountBlinding: pendingDepositData.amountBlinding,
             complianceProof: proofBytes,
             publicValues
           });
@@ -376,7 +379,7 @@ export function PrivateDepositEVM({ privacySystem, userAddress }: PrivateDeposit
     setCurrentStep('done');
     
     // Store commitment data securely
-    proofService.storeCommitmentData(commitment, pendingDepositData!.secret, pendingDepositData!.nullifier);
+    proofService.storeCommitmentData(commitment, pendingDepositData!.secret, pendingDepositData!.nullifier, pendingDepositData!.amountBlinding);
 
     // Get the stored commitment data and add balance information
     const storedData = proofService.getCommitmentData(commitment);
@@ -386,6 +389,7 @@ export function PrivateDepositEVM({ privacySystem, userAddress }: PrivateDeposit
         commitment,
         secret: storedData.secret,
         nullifier: storedData.nullifier,
+        amountBlinding: storedData.amountBlinding,
         timestamp: Date.now(),
         balances: {
           [selectedToken.symbol]: pendingDepositData!.amount // Store the display amount, not wei
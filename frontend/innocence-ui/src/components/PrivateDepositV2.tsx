@@ -202,6 +202,8 @@ export function PrivateDepositV2({ privacySystem, userAddress }: PrivateDepositP
       setPendingDepositData({
         secret,
         nullifier,
+        // Blinds the amount in the deposit's tree leaf; needed to spend the note
+        amountBlinding: proofService.generateSecret(),
         commitment,
         asset: selectedAsset,
         amount: amount
@@ -301,6 +303,7 @@ export function PrivateDepositV2({ privacySystem, userAddress }: PrivateDepositP
       
       const tx = await privacySystem.completeDeposit({
         commitment,
+        amountBlinding: pendingDepositData.amountBlinding,
         complianceProof: proofBytes,
         publicValues: publicValues
       });
@@ -310,7 +313,7 @@ export function PrivateDepositV2({ privacySystem, userAddress }: PrivateDepositP
       console.log('Deposit completed:', receipt);
 
       // Store commitment data securely
-      proofService.storeCommitmentData(commitment, pendingDepositData.secret, pendingDepositData.nullifier);
+      proofService.storeCommitmentData(commitment, pendingDepositData.secret, pendingDepositData.nullifier, pendingDepositData.amountBlinding);
 
       // Update the stored commitment data to include balance information
       const storedData = proofService.getCommitmentData(commitment);
@@ -319,6 +322,7 @@ export function PrivateDepositV2({ privacySystem, userAddress }: PrivateDepositP
           commitment,
           secret: storedData.secret,
           nullifier: storedData.nullifier,
+          amountBlinding: storedData.amountBlinding,
           timestamp: Date.now(),
           balances: {
             [pendingDepositData.asset.symbol]: pendingDepositData.amount // Store the display amount
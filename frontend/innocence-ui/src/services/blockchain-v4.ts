@@ -6,7 +6,7 @@ import { HyperliquidAPI } from './hyperliquidApi';
 const PRIVACY_SYSTEM_V5_ABI = [
   // Two-step deposit functions
   "function prepareDeposit(uint64 token, uint256 amount) external",
-  "function completeDeposit(bytes32 commitment, bytes calldata complianceProof, bytes calldata publicValues) external",
+  "function completeDeposit(bytes32 commitment, bytes32 amountBlinding, bytes calldata complianceProof, bytes calldata publicValues) external",
   "function transferToContract(uint64 token, uint64 amount) external",
   "function canCompleteDeposit(address user) external view returns (bool)",
   "function pendingDeposits(address) external view returns (uint64 token, uint256 amount, uint256 contractBalanceBefore, uint256 timestamp, bool completed)",
@@ -170,6 +170,7 @@ export class PrivacySystemService {
 
   async completeDeposit(params: {
     commitment: string;
+    amountBlinding: string;
     complianceProof: string;
     publicValues: string;
   }): Promise<TransactionResponse> {
@@ -183,6 +184,7 @@ export class PrivacySystemService {
 
     const tx = await this.contract.completeDeposit(
       params.commitment,
+      params.amountBlinding,
       params.complianceProof,
       params.publicValues
     );
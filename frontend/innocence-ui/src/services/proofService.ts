@@ -8,7 +8,10 @@ export interface ProofGenerationParams {
   balance?: number | string;
   minBalance?: number | string;
   assetId?: number;
-  validDays?: number;
+  certificate?: string;
+  issuers?: string;
+  policy?: string;
+  revocationList?: string;
   fromBalance?: number | string;
   toBalance?: number | string;
   fromAsset?: number;
@@ -49,11 +52,12 @@ class ProofService {
   }
 
   // Store commitment data securely in browser
-  storeCommitmentData(commitment: string, secret: string, nullifier: string): void {
+  storeCommitmentData(commitment: string, secret: string, nullifier: string, amountBlinding?: string): void {
     const data = {
       commitment,
       secret,
       nullifier,
+      amountBlinding,
       timestamp: Date.now()
     };
     
@@ -62,14 +66,15 @@ class ProofService {
   }
 
   // Retrieve commitment data
-  getCommitmentData(commitment: string): { secret: string; nullifier: string } | null {
+  getCommitmentData(commitment: string): { secret: string; nullifier: string; amountBlinding?: string } | null {
     const dataStr = localStorage.getItem(`innocence_${commitment}`);
     if (!dataStr) return null;
     
     const data = JSON.parse(dataStr);
     return {
       secret: data.secret,
-      nullifier: data.nullifier
+      nullifier: data.nullifier,
+      amountBlinding: data.amountBlinding
     };
   }
 
@@ -190,8 +195,9 @@ class ProofService {
     }
   }
 
-  // Helper for deposit with compliance proof
-  async generateDepositProof(secret: string, nullifier: string): Promise<{
+  // Helper for deposit with compliance proof; the certificate is the proof service's
+  // path to the authority's certificate for the commitment
+  async generateDepositProof(secret: string, nullifier: string, certificate?: string): Promise<{
     commitment: string;
     proofBytes: string;
     publicValues: string;
@@ -204,7 +210,9 @@ class ProofService {
         const proofResponse = await this.generateProof('compliance', {
           secret,
           nullifier,
-          validDays: 365
+          certificate,
+          issuers: process.env.REACT_APP_COMPLIANCE_ISSUERS,
+          policy: process.env.REACT_APP_COMPLIANCE_POLICY
         });
 
         // Use the server-formatted proof and encoded public values
@@ -295,8 +303,9 @@ class ProofService {
       return {
         proofBytes: proofResponse.formattedProof || this.proofToBytes(proofResponse.proof),
         publicValues: proofResponse.encodedPublicValues || await this.encodePublicValues('innocence', {
+          mode: 0,
           depositor,
-          sanctionsRoot: proofResponse.status.sanctionsRoot,
+          listRoot: proofResponse.status.sanctionsRoot,
           timestamp: proofResponse.status.timestamp,
           isInnocent: true
         }),
@@ -1,6 +1,6 @@
 const express = require('express');
 const cors = require('cors');
-const { exec } = require('child_process');
+const { execFile } = require('child_process');
 const path = require('path');
 const crypto = require('crypto');
 const { ethers } = require('ethers');
@@ -15,8 +15,15 @@ app.use(express.json());
 
 const ZK_CIRCUITS_PATH = path.join(__dirname, '../zk-circuits/innocence-circuits');
 
-// Helper to execute proof generation binaries
-function executeProofBinary(command) {
+// Helper to execute proof generation binaries with `--prove`
+// Flags go in as `--name=value` without a shell, so request values can't inject commands or flags
+function executeProofBinary(bin, flags) {
+  const args = ['run', '--bin', bin, '--release', '--', '--prove'];
+  for (const [name, value] of Object.entries(flags)) {
+    if (value === undefined || value === null || value === '' || value === false) continue;
+    args.push(value === true ? `--${name}` : `--${name}=${value}`);
+  }
+
   return new Promise((resolve, reject) => {
     const env = {
       ...process.env,
@@ -25,7 +32,7 @@ function executeProofBinary(command) {
       PATH: `/opt/homebrew/opt/go@1.22/bin:${process.env.PATH}`
     };
     
-    exec(command, { cwd: ZK_CIRCUITS_PATH, env, timeout: 600000 }, (error, stdout, stderr) => {
+    execFile('cargo', args, { cwd: ZK_CIRCUITS_PATH, env, timeout: 600000 }, (error, stdout, stderr) => {
       if (error) {
         console.error('Proof generation error:', stderr);
         reject(new Error(stderr || error.message));
@@ -61,16 +68,16 @@ app.post('/api/generate-proof/:proofType', async (req, res) => {
   const params = req.body;
 
   try {
-    let command;
     let publicValues;
     let proof;
 
     switch (proofType) {
       case 'ownership':
         // Generate real ownership proof using SP1
-        command = `cargo run --bin ownership-proof --release -- --prove --secret ${params.secret} --nullifier ${params.nullifier}`;
-
-        const ownershipOutput = await executeProofBinary(command);
+        const ownershipOutput = await executeProofBinary('ownership-proof', {
+          secret: params.secret,
+          nullifier: params.nullifier
+        });
         console.log('Ownership proof generated:', ownershipOutput);
 
         // Read the generated proof file
@@ -106,9 +113,16 @@ app.post('/api/generate-proof/:proofType', async (req, res) => {
 
       case 'balance':
         // Generate real balance proof using SP1
-        command = `cargo run --bin balance-proof --release -- --prove --secret ${params.secret} --nullifier ${params.nullifier} --balance ${params.balance} --min-balance ${params.minBalance} --asset-id ${params.assetId}`;
-
-        const balanceOutput = await executeProofBinary(command);
+        // The note is owned by the spending key, whose note key is the note's secret
+        const balanceOutput = await executeProofBinary('balance-proof', {
+          'spending-key': params.spendingKey,
+          blinding: params.blinding,
+          'amount-blinding': params.amountBlinding,
+          balance: params.balance,
+          'min-balance': params.minBalance,
+          'asset-id': params.assetId,
+          'base-units': true
+        });
         console.log('Balance proof generated:', balanceOutput);
 
         // Read the generated proof file
@@ -123,23 +137,22 @@ app.post('/api/generate-proof/:proofType', async (req, res) => {
           throw new Error('Balance proof file not found');
         }
 
-        const commitmentBal = ethers.keccak256(
-          ethers.AbiCoder.defaultAbiCoder().encode(
-            ['bytes32', 'bytes32'],
-            [params.secret, params.nullifier]
-          )
+        // Public values are exactly what the circuit committed
+        const encodedBalancePublicValues = balanceProofData.publicValues;
+        const [commitmentBal, merkleRootBal, minBalance, assetIdBal] = ethers.AbiCoder.defaultAbiCoder().decode(
+          ['bytes32', 'bytes32', 'uint256', 'uint64'],
+          encodedBalancePublicValues
         );
 
         publicValues = {
           commitment: commitmentBal,
-          merkleRoot: params.merkleRoot || ethers.ZeroHash,
-          minBalance: params.minBalance,
-          assetId: params.assetId
+          merkleRoot: merkleRootBal,
+          minBalance: minBalance.toString(),
+          assetId: assetIdBal.toString()
         };
 
         // Format the proof for SP1VerifierGroth16
         const formattedBalanceProof = formatSP1ProofForVerifier(balanceProofData);
-        const encodedBalancePublicValues = encodePublicValues('balance', publicValues);
         
         return res.json({
           proof,
@@ -152,9 +165,19 @@ app.post('/api/generate-proof/:proofType', async (req, res) => {
 
       case 'compliance':
         // Generate real compliance proof using SP1
-        command = `cargo run --bin compliance-proof --release -- --prove --secret ${params.secret} --nullifier ${params.nullifier} --valid-days ${params.validDays || 365}`;
-
-        const complianceOutput = await executeProofBinary(command);
+        // The certificate (from `issue-certificate`) must be for the commitment of the
+        // secret and nullifier and signed by one of the trusted issuers
+        if (!params.certificate || !params.issuers) {
+          return res.status(400).json({ error: 'Compliance proofs need a certificate and an issuer set' });
+        }
+        const complianceOutput = await executeProofBinary('compliance-proof', {
+          secret: params.secret,
+          nullifier: params.nullifier,
+          certificate: params.certificate,
+          issuers: params.issuers,
+          policy: params.policy,
+          'revocation-list': params.revocationList
+        });
         console.log('Compliance proof generated:', complianceOutput);
 
         // Read the generated proof file
@@ -169,30 +192,34 @@ app.post('/api/generate-proof/:proofType', async (req, res) => {
           throw new Error('Compliance proof file not found');
         }
 
-        const commitmentComp = ethers.keccak256(
-          ethers.AbiCoder.defaultAbiCoder().encode(
-            ['bytes32', 'bytes32'],
-            [params.secret, params.nullifier]
-          )
+        // Public values are exactly what the circuit committed (ComplianceProofPublicValues)
+        const encodedCompliancePublicValues = proofData.publicValues;
+        const compliance = ethers.AbiCoder.defaultAbiCoder().decode(
+          ['uint8', 'bytes32', 'bytes32', 'uint256', 'uint64', 'bytes32', 'bytes32', 'bytes32', 'bool'],
+          encodedCompliancePublicValues
         );
 
         publicValues = {
-          commitment: commitmentComp,
-          complianceAuthority: '0x5Bd2F329C50860366c0E6D3b4227a422B66AD203',
-          validUntil: Math.floor(Date.now() / 1000) + (params.validDays || 365) * 24 * 60 * 60,
-          certificateHash: ethers.keccak256(ethers.toUtf8Bytes('certificate'))
+          mode: Number(compliance[0]),
+          commitment: compliance[1],
+          issuerSetRoot: compliance[2],
+          validUntil: compliance[3].toString(),
+          timestamp: compliance[4].toString(),
+          certificateHash: compliance[5],
+          revocationRoot: compliance[6],
+          predicateHash: compliance[7],
+          predicatesSatisfied: compliance[8]
         };
 
         // Format the proof for SP1VerifierGroth16
         const formattedComplianceProof = formatSP1ProofForVerifier(proofData);
-        const encodedCompliancePublicValues = encodePublicValues('compliance', publicValues);
         
         return res.json({
           proof,
           formattedProof: formattedComplianceProof,
           publicValues,
           encodedPublicValues: encodedCompliancePublicValues,
-          commitment: commitmentComp,
+          commitment: publicValues.commitment,
           proofType: 'compliance',
           timestamp: Date.now()
         });
@@ -201,10 +228,25 @@ app.post('/api/generate-proof/:proofType', async (req, res) => {
         // Generate real trade proof using SP1
         // Include balance parameters for the circuit
         const fromBalance = params.fromBalance || params.depositedAmount || params.fromAmount * 2;
-        const toBalance = params.toBalance || 0;
-        command = `cargo run --bin trade-proof --release -- --prove --secret ${params.secret} --nullifier ${params.nullifier} --from-asset ${params.fromAsset} --to-asset ${params.toAsset} --from-amount ${params.fromAmount} --min-to-amount ${params.minToAmount} --from-balance ${fromBalance} --to-balance ${toBalance}`;
-
-        const tradeOutput = await executeProofBinary(command);
+        // The output is bounded by a signed price: an attestation file from `sign-price`,
+        // or a test price signed with ORACLE_PRIVATE_KEY from the environment
+        if (!params.attestation && !process.env.ORACLE_PRIVATE_KEY) {
+          return res.status(400).json({ error: 'Trade proofs need an attestation or ORACLE_PRIVATE_KEY' });
+        }
+        const tradeOutput = await executeProofBinary('trade-proof', {
+          'spending-key': params.spendingKey,
+          blinding: params.blinding,
+          'amount-blinding': params.amountBlinding,
+          'from-asset': params.fromAsset,
+          'to-asset': params.toAsset,
+          'from-amount': params.fromAmount,
+          'min-to-amount': params.minToAmount,
+          'from-balance': fromBalance,
+          'max-slippage-bps': params.maxSlippageBps || 50,
+          'base-units': true,
+          attestation: params.attestation,
+          tree: params.tree
+        });
         console.log('Trade proof generated:', tradeOutput);
 
         // Read the generated proof file
@@ -219,34 +261,30 @@ app.post('/api/generate-proof/:proofType', async (req, res) => {
           throw new Error('Trade proof file not found');
         }
 
-        const commitmentTrade = ethers.keccak256(
-          ethers.AbiCoder.defaultAbiCoder().encode(
-            ['bytes32', 'bytes32'],
-            [params.secret, params.nullifier]
-          )
+        // Public values are exactly what the circuit committed (SwapNoteProofPublicValues)
+        const encodedTradePublicValues = tradeProofData.publicValues;
+        const trade = ethers.AbiCoder.defaultAbiCoder().decode(
+          ['bytes32', 'bytes32', 'uint64', 'uint64', 'uint256', 'uint256', 'bytes32', 'bytes32', 'bytes32', 'address', 'uint64', 'uint32'],
+          encodedTradePublicValues
         );
 
-        const nullifierHashTrade = ethers.keccak256(params.nullifier);
-        
         publicValues = {
-          commitment: commitmentTrade,
-          nullifierHash: nullifierHashTrade,
-          fromAsset: params.fromAsset,
-          toAsset: params.toAsset,
-          fromAmount: params.fromAmount,
-          minToAmount: params.minToAmount,
-          depositedAmount: params.depositedAmount || params.fromAmount * 2,
-          merkleRoot: params.merkleRoot || ethers.ZeroHash
+          commitment: trade[0],
+          nullifierHash: trade[1],
+          tokenIn: trade[2].toString(),
+          tokenOut: trade[3].toString(),
+          amountIn: trade[4].toString(),
+          minAmountOut: trade[5].toString(),
+          merkleRoot: trade[6],
+          changeLeaf: trade[7],
+          outputLeaf: trade[8],
+          oracle: trade[9],
+          priceTimestamp: trade[10].toString(),
+          maxSlippageBps: trade[11].toString()
         };
         
-        // Override the merkle root if provided in params
-        if (params.merkleRoot) {
-          publicValues.merkleRoot = params.merkleRoot;
-        }
-        
         // Format the proof for SP1VerifierGroth16
         const formattedTradeProof = formatSP1ProofForVerifier(tradeProofData);
-        const encodedTradePublicValues = encodePublicValues('trade', publicValues);
         
         return res.json({
           proof,
@@ -336,9 +374,7 @@ app.post('/api/generate-proof/innocence', async (req, res) => {
     }
     
     // Generate innocence proof using SP1
-    const command = `cargo run --bin innocence-proof --release -- --prove --depositor ${depositor}`;
-    
-    const output = await executeProofBinary(command);
+    const output = await executeProofBinary('innocence-proof', { depositor });
     console.log('Innocence proof generated:', output);
     
     // Read the generated proof file
@@ -351,8 +387,9 @@ app.post('/api/generate-proof/innocence', async (req, res) => {
       const proof = proofData.proof || proofData;
       
       const publicValues = {
+        mode: 0,
         depositor,
-        sanctionsRoot: status.sanctionsRoot,
+        listRoot: status.sanctionsRoot,
         timestamp: status.timestamp,
         isInnocent: true
       };
@@ -94,12 +94,17 @@ function encodePublicValues(proofType, publicValues) {
   switch (proofType) {
     case 'compliance':
       return abiCoder.encode(
-        ['bytes32', 'address', 'uint256', 'bytes32'],
+        ['uint8', 'bytes32', 'bytes32', 'uint256', 'uint64', 'bytes32', 'bytes32', 'bytes32', 'bool'],
         [
+          publicValues.mode,
           publicValues.commitment,
-          publicValues.complianceAuthority,
+          publicValues.issuerSetRoot,
           publicValues.validUntil,
-          publicValues.certificateHash
+          publicValues.timestamp,
+          publicValues.certificateHash,
+          publicValues.revocationRoot,
+          publicValues.predicateHash,
+          publicValues.predicatesSatisfied
         ]
       );
       
@@ -116,25 +121,30 @@ function encodePublicValues(proofType, publicValues) {
       
     case 'trade':
       return abiCoder.encode(
-        ['bytes32', 'bytes32', 'uint64', 'uint64', 'uint256', 'uint256', 'uint256', 'bytes32'],
+        ['bytes32', 'bytes32', 'uint64', 'uint64', 'uint256', 'uint256', 'bytes32', 'bytes32', 'bytes32', 'address', 'uint64', 'uint32'],
         [
           publicValues.commitment,
           publicValues.nullifierHash,
-          publicValues.fromAsset,
-          publicValues.toAsset,
-          publicValues.fromAmount,
-          publicValues.minToAmount,
-          publicValues.depositedAmount,
-          publicValues.merkleRoot
+          publicValues.tokenIn,
+          publicValues.tokenOut,
+          publicValues.amountIn,
+          publicValues.minAmountOut,
+          publicValues.merkleRoot,
+          publicValues.changeLeaf,
+          publicValues.outputLeaf,
+          publicValues.oracle,
+          publicValues.priceTimestamp,
+          publicValues.maxSlippageBps
         ]
       );
       
     case 'innocence':
       return abiCoder.encode(
-        ['address', 'bytes32', 'uint256', 'bool'],
+        ['uint8', 'address', 'bytes32', 'uint256', 'bool'],
         [
+          publicValues.mode,
           publicValues.depositor,
-          publicValues.sanctionsRoot,
+          publicValues.listRoot,
           publicValues.timestamp,
           publicValues.isInnocent
         ]
@@ -1,24 +1,54 @@
 //! Compliance Proof Circuit
 //! 
 //! This circuit proves that a user has valid KYC/compliance certification
-//! without revealing their identity or personal information.
+//! without revealing their identity or personal information. The first input is
+//! the ComplianceMode, which selects the credential format.
 //!
-//! Public inputs: commitment, compliance_authority_address, valid_until, certificate_hash
-//! Private inputs: secret, nullifier, certificate_data, authority_signature
+//! Certificate mode
+//! Public inputs: commitment, issuer_set_root, current_timestamp, revocation_root, policy
+//! Private inputs: secret, nullifier, certificate (canonical encoding), authority_signature,
+//!                 revocation non-membership proof, issuer membership proof
 //! Proof: 
 //! 1. commitment = hash(secret || nullifier)
-//! 2. certificate_hash = hash(certificate_data)
-//! 3. Verify authority signature on certificate
-//! 4. Check certificate is still valid (timestamp check)
+//! 2. The certificate decodes as a supported ComplianceCertificate version
+//! 3. The certificate's subject is commitment and its issuer is in the issuer set
+//!    committed to by issuer_set_root, without revealing which issuer
+//! 4. The certificate is signed by its issuer (EIP-191 over the certificate hash)
+//! 5. current_timestamp is within the certificate's validity period
+//! 6. The certificate hash is not in the revocation tree committed to by revocation_root
+//! 7. Evaluate the policy's predicates on the certificate attributes; only the
+//!    policy hash and the result are committed
+//!
+//! Credential mode
+//! Public inputs: commitment, current_timestamp, policy
+//! Private inputs: secret, nullifier, credential (ES256K compact JWS), issuer public key
+//! Proof:
+//! 1. commitment = hash(secret || nullifier)
+//! 2. The credential is signed by the issuer key; only the key's JWK thumbprint is committed
+//! 3. The credential's `sub` claim is commitment
+//! 4. current_timestamp is within the credential's nbf and exp
+//! 5. Evaluate the policy on the credential's attribute claims, as in certificate mode
 
 #![no_main]
 sp1_zkvm::entrypoint!(main);
 
 use alloy_sol_types::SolType;
-use innocence_circuits_lib::ComplianceProofPublicValues;
-use sha2::{Sha256, Digest};
+use innocence_circuits_lib::credential::jwk_thumbprint;
+use innocence_circuits_lib::issuers::verify_issuer;
+use innocence_circuits_lib::{
+    compute_commitment, ComplianceCertificate, ComplianceMode, ComplianceProofPublicValues, Credential,
+    CredentialProofPublicValues, MerkleProof, Policy, RevocationProof,
+};
 
 pub fn main() {
+    let mode: ComplianceMode = sp1_zkvm::io::read();
+    match mode {
+        ComplianceMode::Certificate => prove_certificate(),
+        ComplianceMode::Credential => prove_credential(),
+    }
+}
+
+fn prove_certificate() {
     // Read private inputs
     let secret: [u8; 32] = sp1_zkvm::io::read();
     let nullifier: [u8; 32] = sp1_zkvm::io::read();
@@ -27,18 +57,20 @@ pub fn main() {
     let signature_v: u8 = sp1_zkvm::io::read();
     let signature_r: [u8; 32] = sp1_zkvm::io::read();
     let signature_s: [u8; 32] = sp1_zkvm::io::read();
+    // Adjacent leaves of the revocation tree around the certificate hash
+    let revocation_proof: RevocationProof = sp1_zkvm::io::read();
+    // Path of the certificate issuer in the issuer tree
+    let issuer_proof: MerkleProof = sp1_zkvm::io::read();
     
     // Read public inputs
     let expected_commitment: [u8; 32] = sp1_zkvm::io::read();
-    let compliance_authority: [u8; 20] = sp1_zkvm::io::read(); // Ethereum address
-    let valid_until: u64 = sp1_zkvm::io::read();
+    let issuer_set_root: [u8; 32] = sp1_zkvm::io::read();
     let current_timestamp: u64 = sp1_zkvm::io::read();
+    let revocation_root: [u8; 32] = sp1_zkvm::io::read();
+    let policy: Policy = sp1_zkvm::io::read();
     
     // Step 1: Verify commitment
-    let mut hasher = Sha256::new();
-    hasher.update(&secret);
-    hasher.update(&nullifier);
-    let computed_commitment: [u8; 32] = hasher.finalize().into();
+    let computed_commitment = compute_commitment(&secret, &nullifier);
     
     assert_eq!(
         computed_commitment, 
@@ -46,72 +78,135 @@ pub fn main() {
         "Invalid commitment"
     );
     
-    // Step 2: Compute certificate hash
-    let mut cert_hasher = Sha256::new();
-    cert_hasher.update(&certificate_data);
-    let certificate_hash: [u8; 32] = cert_hasher.finalize().into();
-    
-    // Step 3: Verify certificate contains user commitment
-    // Certificate data format: [commitment(32) || valid_until(8) || user_data(...)]
-    assert!(
-        certificate_data.len() >= 40,
-        "Invalid certificate data length"
-    );
+    // Step 2: Parse the certificate
+    let certificate = match ComplianceCertificate::decode(&certificate_data) {
+        Ok(certificate) => certificate,
+        Err(e) => panic!("Invalid certificate: {}", e),
+    };
     
-    let cert_commitment = &certificate_data[0..32];
+    // Step 3: Verify certificate subject and issuer
     assert_eq!(
-        cert_commitment,
-        &expected_commitment,
+        certificate.subject,
+        expected_commitment,
         "Certificate commitment mismatch"
     );
-    
-    let cert_valid_until = u64::from_le_bytes(
-        certificate_data[32..40].try_into().unwrap()
+    assert!(
+        verify_issuer(&certificate.issuer, &issuer_proof, &issuer_set_root),
+        "Certificate issuer is not in the issuer set"
     );
-    assert_eq!(
-        cert_valid_until,
-        valid_until,
-        "Certificate validity period mismatch"
+    
+    // Step 4: Verify authority signature
+    // The authority signs the certificate hash with personal_sign (EIP-191),
+    // recovery runs on SP1's secp256k1 precompile
+    assert!(
+        certificate.verify_signature(signature_v, &signature_r, &signature_s).is_ok(),
+        "Certificate not signed by its issuer"
     );
     
-    // Step 4: Check certificate is still valid
+    // Step 5: Check certificate is currently valid
     assert!(
-        current_timestamp <= valid_until,
+        current_timestamp >= certificate.issued_at,
+        "Certificate is not yet valid"
+    );
+    assert!(
+        current_timestamp <= certificate.expires_at,
         "Certificate has expired"
     );
     
-    // Step 5: Verify authority signature
-    // In a real implementation, we would:
-    // 1. Recover signer address from signature
-    // 2. Verify it matches compliance_authority
-    // For now, we'll do a simplified check
-    
-    // Create message hash for signature verification
-    let mut msg_hasher = Sha256::new();
-    msg_hasher.update(&certificate_data);
-    let message_hash: [u8; 32] = msg_hasher.finalize().into();
-    
-    // Simplified signature verification
-    // In production, use proper ECDSA recovery
-    assert_ne!(
-        signature_v,
-        0,
-        "Invalid signature recovery ID"
-    );
-    assert_ne!(
-        signature_r[0],
-        0,
-        "Invalid signature r component"
+    // Step 6: Check the certificate has not been revoked
+    let certificate_hash = certificate.hash();
+    assert!(
+        revocation_proof.verify(&certificate_hash, &revocation_root),
+        "Certificate is revoked or the revocation proof is invalid"
     );
     
+    // Step 7: Evaluate the policy without revealing the attributes
+    if let Err(e) = policy.validate() {
+        panic!("Invalid policy: {}", e);
+    }
+    let predicates_satisfied = policy.evaluate(&certificate.attributes, current_timestamp);
+    
     // Commit to public values
     let public_values = ComplianceProofPublicValues {
+        mode: ComplianceMode::Certificate as u8,
         commitment: expected_commitment.into(),
-        complianceAuthority: compliance_authority.into(),
-        validUntil: alloy_sol_types::private::U256::from(valid_until),
+        issuerSetRoot: issuer_set_root.into(),
+        validUntil: alloy_sol_types::private::U256::from(certificate.expires_at),
+        timestamp: current_timestamp,
         certificateHash: certificate_hash.into(),
+        revocationRoot: revocation_root.into(),
+        predicateHash: policy.hash().into(),
+        predicatesSatisfied: predicates_satisfied,
     };
     
     let bytes = ComplianceProofPublicValues::abi_encode(&public_values);
     sp1_zkvm::io::commit_slice(&bytes);
+}
+
+fn prove_credential() {
+    // Read private inputs
+    let secret: [u8; 32] = sp1_zkvm::io::read();
+    let nullifier: [u8; 32] = sp1_zkvm::io::read();
+    let token: String = sp1_zkvm::io::read();
+    // SEC1-encoded secp256k1 public key of the credential issuer
+    let issuer_key: Vec<u8> = sp1_zkvm::io::read();
+    
+    // Read public inputs
+    let expected_commitment: [u8; 32] = sp1_zkvm::io::read();
+    let current_timestamp: u64 = sp1_zkvm::io::read();
+    let policy: Policy = sp1_zkvm::io::read();
+    
+    // Step 1: Verify commitment
+    let computed_commitment = compute_commitment(&secret, &nullifier);
+    
+    assert_eq!(
+        computed_commitment, 
+        expected_commitment, 
+        "Invalid commitment"
+    );
+    
+    // Step 2: Parse the credential and verify the issuer's ES256K signature
+    let credential = match Credential::parse(&token) {
+        Ok(credential) => credential,
+        Err(e) => panic!("Invalid credential: {}", e),
+    };
+    if let Err(e) = credential.verify(&issuer_key) {
+        panic!("Invalid credential: {}", e);
+    }
+    let issuer_key_hash = match jwk_thumbprint(&issuer_key) {
+        Ok(hash) => hash,
+        Err(e) => panic!("Invalid credential: {}", e),
+    };
+    
+    // Step 3: The credential is bound to the commitment
+    assert_eq!(
+        credential.claims.subject,
+        expected_commitment,
+        "Credential subject mismatch"
+    );
+    
+    // Step 4: Check the credential is currently valid
+    assert!(
+        credential.claims.is_valid_at(current_timestamp),
+        "Credential is expired or not yet valid"
+    );
+    
+    // Step 5: Evaluate the policy without revealing the attributes
+    if let Err(e) = policy.validate() {
+        panic!("Invalid policy: {}", e);
+    }
+    let predicates_satisfied = policy.evaluate(&credential.claims.attributes, current_timestamp);
+    
+    // Commit to public values
+    let public_values = CredentialProofPublicValues {
+        mode: ComplianceMode::Credential as u8,
+        commitment: expected_commitment.into(),
+        issuerKeyHash: issuer_key_hash.into(),
+        timestamp: current_timestamp,
+        predicateHash: policy.hash().into(),
+        predicatesSatisfied: predicates_satisfied,
+    };
+    
+    let bytes = CredentialProofPublicValues::abi_encode(&public_values);
+    sp1_zkvm::io::commit_slice(&bytes);
 }
\ No newline at end of file
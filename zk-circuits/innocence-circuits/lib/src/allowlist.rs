@@ -0,0 +1,112 @@
+//! Allowlist tree
+//!
+//! In allowlist mode the innocence circuit proves the depositor IS on a list of
+//! approved addresses rather than NOT on the sanctions list. The allowlist is a
+//! sorted tree like the sanctions list (see `sorted_tree`), so the same snapshots
+//! build it; the membership witness is the Merkle path of the depositor's leaf.
+//!
+//! Allowlist leaves are domain separated, so a tree built from a sanctions list
+//! never has the same root as the allowlist of the same addresses, and a path in
+//! the sanctions tree does not prove allowlist membership.
+
+use crate::sanctions::{MAX_SENTINEL, MIN_SENTINEL};
+use crate::sorted_tree::{SortedKey, SortedTree};
+use crate::{hash, MerkleProof};
+use serde::{Deserialize, Serialize};
+
+/// Domain separator for allowlist leaves
+const ALLOWLIST_LEAF_DOMAIN: &[u8] = b"innocence-allowlist";
+
+/// List the innocence circuit checks the depositor against, its first input and
+/// first public value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InnocenceMode {
+    /// Non-membership in the sanctions tree
+    Sanctions = 0,
+    /// Membership in an allowlist tree
+    Allowlist = 1,
+}
+
+/// Address keyed into the allowlist tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AllowlistedAddress(pub [u8; 20]);
+
+impl AsRef<[u8]> for AllowlistedAddress {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl SortedKey for AllowlistedAddress {
+    const MIN: Self = AllowlistedAddress(MIN_SENTINEL);
+    const MAX: Self = AllowlistedAddress(MAX_SENTINEL);
+
+    fn leaf(&self) -> [u8; 32] {
+        compute_allowlist_leaf(&self.0)
+    }
+}
+
+/// Sorted allowlist tree, built on the host from the full list
+pub type AllowlistTree = SortedTree<AllowlistedAddress>;
+
+impl AllowlistTree {
+    /// Build the canonical tree from a list of addresses in any order
+    pub fn from_addresses(addresses: &[[u8; 20]]) -> Self {
+        let keys: Vec<AllowlistedAddress> = addresses.iter().copied().map(AllowlistedAddress).collect();
+        Self::new(&keys)
+    }
+}
+
+/// Hash an address into an allowlist leaf: hash("innocence-allowlist" ‖ address)
+pub fn compute_allowlist_leaf(address: &[u8; 20]) -> [u8; 32] {
+    let mut data = Vec::with_capacity(ALLOWLIST_LEAF_DOMAIN.len() + 20);
+    data.extend_from_slice(ALLOWLIST_LEAF_DOMAIN);
+    data.extend_from_slice(address);
+    hash::hash(&data)
+}
+
+/// Check that `proof` is the path of `address`'s leaf in the allowlist tree
+/// committed to by `root`
+pub fn verify_allowlisted(address: &[u8; 20], proof: &MerkleProof, root: &[u8; 32]) -> bool {
+    // The sentinels bracket every tree but are not listed addresses
+    if *address == MIN_SENTINEL || *address == MAX_SENTINEL {
+        return false;
+    }
+    proof.leaf == compute_allowlist_leaf(address) && proof.verify(root).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sanctions::{compute_address_leaf, SanctionsTree};
+
+    const ALICE: [u8; 20] = [0x11; 20];
+    const BOB: [u8; 20] = [0x22; 20];
+
+    #[test]
+    fn sanctions_leaves_never_collide_with_allowlist_leaves() {
+        assert_ne!(compute_address_leaf(&ALICE), compute_allowlist_leaf(&ALICE));
+
+        // ALICE's sanctions path is a valid path, just not an allowlist one, under either root
+        let sanctions = SanctionsTree::new(&[ALICE, BOB]);
+        let allowlist = AllowlistTree::from_addresses(&[ALICE, BOB]);
+        let sanctions_path = sanctions.membership_proof(&ALICE).unwrap();
+        assert!(sanctions_path.verify(&sanctions.root()).is_ok());
+        assert_ne!(allowlist.root(), sanctions.root());
+        assert!(!verify_allowlisted(&ALICE, &sanctions_path, &sanctions.root()));
+        assert!(!verify_allowlisted(&ALICE, &sanctions_path, &allowlist.root()));
+    }
+
+    #[test]
+    fn sentinel_paths_are_not_membership() {
+        let tree = AllowlistTree::from_addresses(&[ALICE]);
+        assert!(tree.membership_proof(&AllowlistedAddress(MIN_SENTINEL)).is_none());
+
+        // Hand-built paths to the sentinel leaves hash to the root, but are rejected
+        for (index, sentinel) in [(0, MIN_SENTINEL), (2, MAX_SENTINEL)] {
+            let path = tree.proof(index);
+            assert!(path.verify(&tree.root()).is_ok());
+            assert!(!verify_allowlisted(&sentinel, &path, &tree.root()));
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! Token amounts
+//!
+//! Amounts are `uint256` values in an asset's base units, the same as the ERC20
+//! contracts, so 18-decimal tokens are never truncated. Operators write amounts as
+//! decimal strings such as `1.5`, which `parse_units` scales by the asset's decimals.
+
+pub use alloy_primitives::U256;
+use std::fmt;
+
+/// Most decimals an asset can have: 10^77 is the largest power of ten below 2^256
+pub const MAX_DECIMALS: u8 = 77;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AmountError {
+    /// No digits
+    Empty,
+    /// Anything other than digits and a single decimal point
+    InvalidCharacter(char),
+    /// More fractional digits than the asset has decimals
+    TooManyDecimals { decimals: u8 },
+    /// Asset decimals above `MAX_DECIMALS`
+    InvalidDecimals(u8),
+    /// Value does not fit in 256 bits
+    Overflow,
+}
+
+impl fmt::Display for AmountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AmountError::Empty => write!(f, "amount is empty"),
+            AmountError::InvalidCharacter(c) => write!(f, "invalid character {:?} in amount", c),
+            AmountError::TooManyDecimals { decimals } => {
+                write!(f, "amount has more than {} decimal places", decimals)
+            }
+            AmountError::InvalidDecimals(decimals) => {
+                write!(f, "{} decimals exceeds maximum {}", decimals, MAX_DECIMALS)
+            }
+            AmountError::Overflow => write!(f, "amount does not fit in 256 bits"),
+        }
+    }
+}
+
+impl std::error::Error for AmountError {}
+
+fn pow10(exp: u8) -> U256 {
+    U256::from(10u64).pow(U256::from(exp))
+}
+
+/// Parse a decimal string (`"1.5"`, `"1000"`, `".25"`) into base units of an asset
+/// with `decimals` decimals. Trailing fractional zeros beyond the decimals are accepted.
+pub fn parse_units(s: &str, decimals: u8) -> Result<U256, AmountError> {
+    if decimals > MAX_DECIMALS {
+        return Err(AmountError::InvalidDecimals(decimals));
+    }
+
+    let s = s.trim();
+    let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(AmountError::Empty);
+    }
+    if let Some(c) = whole.chars().chain(fraction.chars()).find(|c| !c.is_ascii_digit()) {
+        return Err(AmountError::InvalidCharacter(c));
+    }
+
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals as usize {
+        return Err(AmountError::TooManyDecimals { decimals });
+    }
+
+    // Digits of whole || fraction, padded to `decimals` fractional places
+    let padding = decimals as usize - fraction.len();
+    let mut value = U256::ZERO;
+    for digit in whole.bytes().chain(fraction.bytes()) {
+        value = value
+            .checked_mul(U256::from(10u64))
+            .and_then(|v| v.checked_add(U256::from(digit - b'0')))
+            .ok_or(AmountError::Overflow)?;
+    }
+    value.checked_mul(pow10(padding as u8)).ok_or(AmountError::Overflow)
+}
+
+/// Decimal string for `amount` base units, without trailing fractional zeros
+pub fn format_units(amount: U256, decimals: u8) -> String {
+    if decimals == 0 || decimals > MAX_DECIMALS {
+        return amount.to_string();
+    }
+
+    let (whole, fraction) = amount.div_rem(pow10(decimals));
+    let fraction = format!("{:0>width$}", fraction.to_string(), width = decimals as usize);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
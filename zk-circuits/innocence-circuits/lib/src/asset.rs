@@ -0,0 +1,223 @@
+//! Asset registry
+//!
+//! One table of the assets the protocol handles, so the asset ids in proofs agree
+//! with the contracts' `tokenAddresses`. Each asset has a symbol, its EVM decimals
+//! (the unit of every amount in a proof), its HyperCore token index and its ERC20
+//! address per HyperEVM network. The hosts resolve `--asset USDC` through the
+//! registry and refuse ids it does not list.
+//!
+//! The registry is `assets.toml` at the workspace root, compiled in as the
+//! default; operators can load a TOML or JSON file with the same shape instead.
+
+use crate::amount::{format_units, parse_units, AmountError, MAX_DECIMALS, U256};
+use crate::signature::parse_evm_address;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Asset id as used in proofs and in the contracts' `tokenAddresses`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AssetId(pub u64);
+
+impl fmt::Display for AssetId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u64> for AssetId {
+    fn from(id: u64) -> Self {
+        AssetId(id)
+    }
+}
+
+impl From<AssetId> for u64 {
+    fn from(id: AssetId) -> Self {
+        id.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Asset {
+    pub id: AssetId,
+    pub symbol: String,
+    /// EVM decimals; proof amounts are in these base units
+    pub decimals: u8,
+    /// Deposited as the native gas token rather than an ERC20
+    #[serde(default)]
+    pub native: bool,
+    /// HyperCore spot token index, if the asset is linked to HyperCore
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hypercore_index: Option<u32>,
+    /// ERC20 address per network name
+    #[serde(default)]
+    pub evm: BTreeMap<String, String>,
+}
+
+impl Asset {
+    /// ERC20 address on `network`
+    pub fn evm_address(&self, network: &str) -> Option<[u8; 20]> {
+        self.evm.get(network).and_then(|address| parse_evm_address(address))
+    }
+
+    /// Base units for a decimal amount of this asset, e.g. `1.5`
+    pub fn parse_amount(&self, amount: &str) -> Result<U256, AmountError> {
+        parse_units(amount, self.decimals)
+    }
+
+    /// Decimal string for `amount` base units of this asset
+    pub fn format_amount(&self, amount: U256) -> String {
+        format_units(amount, self.decimals)
+    }
+}
+
+impl fmt::Display for Asset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (id {})", self.symbol, self.id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    DuplicateId(AssetId),
+    DuplicateSymbol(String),
+    InvalidDecimals { symbol: String, decimals: u8 },
+    InvalidAddress { symbol: String, network: String },
+    /// No asset with this symbol or id
+    UnknownAsset(String),
+    UnknownAssetId(AssetId),
+    /// Registry file could not be read or parsed
+    Load(String),
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::DuplicateId(id) => write!(f, "asset id {} is listed twice", id),
+            RegistryError::DuplicateSymbol(symbol) => write!(f, "asset symbol {} is listed twice", symbol),
+            RegistryError::InvalidDecimals { symbol, decimals } => {
+                write!(f, "{} has {} decimals, maximum is {}", symbol, decimals, MAX_DECIMALS)
+            }
+            RegistryError::InvalidAddress { symbol, network } => {
+                write!(f, "{} has an invalid address on {}", symbol, network)
+            }
+            RegistryError::UnknownAsset(asset) => write!(f, "unknown asset {}", asset),
+            RegistryError::UnknownAssetId(id) => write!(f, "asset id {} is not in the registry", id),
+            RegistryError::Load(e) => write!(f, "invalid asset registry: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+/// Validated set of assets with unique ids and symbols
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RegistryFile", into = "RegistryFile")]
+pub struct AssetRegistry {
+    assets: Vec<Asset>,
+}
+
+impl AssetRegistry {
+    pub fn new(mut assets: Vec<Asset>) -> Result<Self, RegistryError> {
+        assets.sort_by_key(|asset| asset.id);
+        for (i, asset) in assets.iter().enumerate() {
+            if i > 0 && assets[i - 1].id == asset.id {
+                return Err(RegistryError::DuplicateId(asset.id));
+            }
+            if assets[..i].iter().any(|a| a.symbol.eq_ignore_ascii_case(&asset.symbol)) {
+                return Err(RegistryError::DuplicateSymbol(asset.symbol.clone()));
+            }
+            if asset.decimals > MAX_DECIMALS {
+                return Err(RegistryError::InvalidDecimals {
+                    symbol: asset.symbol.clone(),
+                    decimals: asset.decimals,
+                });
+            }
+            if let Some(network) = asset.evm.iter().find(|(_, a)| parse_evm_address(a).is_none()).map(|(n, _)| n) {
+                return Err(RegistryError::InvalidAddress {
+                    symbol: asset.symbol.clone(),
+                    network: network.clone(),
+                });
+            }
+        }
+        Ok(Self { assets })
+    }
+
+    /// Assets in id order
+    pub fn assets(&self) -> &[Asset] {
+        &self.assets
+    }
+
+    pub fn get(&self, id: AssetId) -> Option<&Asset> {
+        self.assets.iter().find(|asset| asset.id == id)
+    }
+
+    /// Asset by symbol, ignoring case
+    pub fn by_symbol(&self, symbol: &str) -> Option<&Asset> {
+        self.assets.iter().find(|asset| asset.symbol.eq_ignore_ascii_case(symbol))
+    }
+
+    /// Asset for a registered id, or an error naming the id
+    pub fn check(&self, id: AssetId) -> Result<&Asset, RegistryError> {
+        self.get(id).ok_or(RegistryError::UnknownAssetId(id))
+    }
+
+    /// Asset for a command-line value: a symbol (`USDC`) or a numeric id (`4`)
+    pub fn resolve(&self, asset: &str) -> Result<&Asset, RegistryError> {
+        let asset = asset.trim();
+        match asset.parse::<u64>() {
+            Ok(id) => self.check(AssetId(id)),
+            Err(_) => self
+                .by_symbol(asset)
+                .ok_or_else(|| RegistryError::UnknownAsset(asset.to_string())),
+        }
+    }
+}
+
+#[cfg(feature = "registry")]
+impl AssetRegistry {
+    pub fn from_toml(data: &str) -> Result<Self, RegistryError> {
+        toml::from_str(data).map_err(|e| RegistryError::Load(e.to_string()))
+    }
+
+    pub fn from_json(data: &str) -> Result<Self, RegistryError> {
+        serde_json::from_str(data).map_err(|e| RegistryError::Load(e.to_string()))
+    }
+
+    /// Load a `.json` registry, or TOML for any other extension
+    pub fn load(path: &std::path::Path) -> Result<Self, RegistryError> {
+        let data = std::fs::read_to_string(path)
+            .map_err(|e| RegistryError::Load(format!("{}: {}", path.display(), e)))?;
+        if path.extension().is_some_and(|ext| ext == "json") {
+            Self::from_json(&data)
+        } else {
+            Self::from_toml(&data)
+        }
+    }
+
+    /// The workspace `assets.toml`
+    pub fn builtin() -> Self {
+        Self::from_toml(include_str!("../../assets.toml")).expect("assets.toml is invalid")
+    }
+}
+
+/// On-disk form of `AssetRegistry`
+#[derive(Serialize, Deserialize)]
+struct RegistryFile {
+    assets: Vec<Asset>,
+}
+
+impl From<AssetRegistry> for RegistryFile {
+    fn from(registry: AssetRegistry) -> Self {
+        Self { assets: registry.assets }
+    }
+}
+
+impl TryFrom<RegistryFile> for AssetRegistry {
+    type Error = RegistryError;
+
+    fn try_from(file: RegistryFile) -> Result<Self, Self::Error> {
+        AssetRegistry::new(file.assets)
+    }
+}
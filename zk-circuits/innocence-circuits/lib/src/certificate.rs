@@ -0,0 +1,489 @@
+//! Compliance certificates
+//!
+//! A compliance authority attests that the holder of a subject commitment passed
+//! KYC, with typed attributes the compliance circuit can check. Certificates have
+//! one canonical encoding, the ABI encoding of the `ComplianceCertificate` struct
+//! below; that is what the circuit reads and what host tools store.
+//!
+//! The certificate hash is the EIP-712 struct hash, whose type hash separates it
+//! from every other signed message. The issuer signs it with `personal_sign`
+//! (EIP-191), as oracles sign price attestations.
+
+use crate::signature::{eip191_digest, recover_signer};
+use alloy_sol_types::{SolStruct, SolValue};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+mod encoding {
+    alloy_sol_types::sol! {
+        struct ComplianceCertificate {
+            uint16 version;
+            address issuer;
+            bytes32 subject;
+            uint64 issuedAt;
+            uint64 expiresAt;
+            bytes2 jurisdiction;
+            uint8 accreditationLevel;
+            uint8 kycTier;
+            uint32 dateOfBirth;
+        }
+    }
+}
+
+/// Version written by this library; decoding rejects any other
+pub const CERTIFICATE_VERSION: u16 = 1;
+
+/// Credential format checked by the compliance circuit, its first input and
+/// public value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ComplianceMode {
+    /// `ComplianceCertificate` signed with personal_sign
+    Certificate = 0,
+    /// ES256K compact JWS (JWT or W3C VC-JWT), see `credential`
+    Credential = 1,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CertificateError {
+    /// Not a canonical certificate encoding
+    Malformed,
+    UnsupportedVersion(u16),
+    InvalidJurisdiction(String),
+    InvalidAccreditationLevel(String),
+    InvalidKycTier(String),
+    InvalidDate(String),
+    /// Expires before it was issued
+    InvalidValidity { issued_at: u64, expires_at: u64 },
+    /// Signature is malformed or not by the certificate's issuer
+    InvalidSignature,
+}
+
+impl fmt::Display for CertificateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CertificateError::Malformed => write!(f, "malformed certificate encoding"),
+            CertificateError::UnsupportedVersion(v) => {
+                write!(f, "certificate version {} is not supported (expected {})", v, CERTIFICATE_VERSION)
+            }
+            CertificateError::InvalidJurisdiction(j) => write!(f, "invalid jurisdiction {}", j),
+            CertificateError::InvalidAccreditationLevel(a) => write!(f, "invalid accreditation level {}", a),
+            CertificateError::InvalidKycTier(t) => write!(f, "invalid KYC tier {}", t),
+            CertificateError::InvalidDate(d) => write!(f, "invalid date {}", d),
+            CertificateError::InvalidValidity { issued_at, expires_at } => {
+                write!(f, "certificate expires at {} before it is issued at {}", expires_at, issued_at)
+            }
+            CertificateError::InvalidSignature => write!(f, "certificate is not signed by its issuer"),
+        }
+    }
+}
+
+impl std::error::Error for CertificateError {}
+
+/// ISO 3166-1 alpha-2 country code, e.g. `CH`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Jurisdiction([u8; 2]);
+
+impl Jurisdiction {
+    pub fn new(code: &str) -> Result<Self, CertificateError> {
+        match code.as_bytes() {
+            [a, b] if a.is_ascii_uppercase() && b.is_ascii_uppercase() => Ok(Jurisdiction([*a, *b])),
+            _ => Err(CertificateError::InvalidJurisdiction(code.to_string())),
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("jurisdiction is ASCII")
+    }
+
+    pub fn as_bytes(&self) -> [u8; 2] {
+        self.0
+    }
+}
+
+impl fmt::Display for Jurisdiction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Jurisdiction {
+    type Err = CertificateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Jurisdiction::new(&s.trim().to_ascii_uppercase())
+    }
+}
+
+impl TryFrom<String> for Jurisdiction {
+    type Error = CertificateError;
+
+    fn try_from(code: String) -> Result<Self, Self::Error> {
+        Jurisdiction::new(&code)
+    }
+}
+
+impl From<Jurisdiction> for String {
+    fn from(jurisdiction: Jurisdiction) -> Self {
+        jurisdiction.code().to_string()
+    }
+}
+
+/// Investor accreditation, ordered from least to most qualified
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccreditationLevel {
+    None = 0,
+    Accredited = 1,
+    Qualified = 2,
+    Institutional = 3,
+}
+
+impl TryFrom<u8> for AccreditationLevel {
+    type Error = CertificateError;
+
+    fn try_from(level: u8) -> Result<Self, Self::Error> {
+        match level {
+            0 => Ok(AccreditationLevel::None),
+            1 => Ok(AccreditationLevel::Accredited),
+            2 => Ok(AccreditationLevel::Qualified),
+            3 => Ok(AccreditationLevel::Institutional),
+            _ => Err(CertificateError::InvalidAccreditationLevel(level.to_string())),
+        }
+    }
+}
+
+impl FromStr for AccreditationLevel {
+    type Err = CertificateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "none" => Ok(AccreditationLevel::None),
+            "accredited" => Ok(AccreditationLevel::Accredited),
+            "qualified" => Ok(AccreditationLevel::Qualified),
+            "institutional" => Ok(AccreditationLevel::Institutional),
+            _ => Err(CertificateError::InvalidAccreditationLevel(s.to_string())),
+        }
+    }
+}
+
+/// Depth of identity verification, ordered from least to most thorough
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KycTier {
+    None = 0,
+    Basic = 1,
+    Standard = 2,
+    Enhanced = 3,
+}
+
+impl TryFrom<u8> for KycTier {
+    type Error = CertificateError;
+
+    fn try_from(tier: u8) -> Result<Self, Self::Error> {
+        match tier {
+            0 => Ok(KycTier::None),
+            1 => Ok(KycTier::Basic),
+            2 => Ok(KycTier::Standard),
+            3 => Ok(KycTier::Enhanced),
+            _ => Err(CertificateError::InvalidKycTier(tier.to_string())),
+        }
+    }
+}
+
+impl FromStr for KycTier {
+    type Err = CertificateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "none" => Ok(KycTier::None),
+            "basic" => Ok(KycTier::Basic),
+            "standard" => Ok(KycTier::Standard),
+            "enhanced" => Ok(KycTier::Enhanced),
+            _ => Err(CertificateError::InvalidKycTier(s.to_string())),
+        }
+    }
+}
+
+/// Calendar date, encoded as the integer YYYYMMDD
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CivilDate(u32);
+
+impl CivilDate {
+    pub fn new(year: u32, month: u32, day: u32) -> Result<Self, CertificateError> {
+        let leap = matches!((year % 4, year % 100, year % 400), (0, 1.., _) | (_, _, 0));
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => 0,
+        };
+        if !(1..=9999).contains(&year) || day == 0 || day > days_in_month {
+            return Err(CertificateError::InvalidDate(format!("{:04}-{:02}-{:02}", year, month, day)));
+        }
+        Ok(CivilDate(year * 10_000 + month * 100 + day))
+    }
+
+    /// UTC date of a unix timestamp
+    pub fn from_timestamp(timestamp: u64) -> Self {
+        // Civil-from-days (Howard Hinnant), shifted so the year starts in March
+        let z = timestamp / 86_400 + 719_468;
+        let era = z / 146_097;
+        let doe = z % 146_097;
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + u64::from(month <= 2);
+        CivilDate((year * 10_000 + month * 100 + day) as u32)
+    }
+
+    /// YYYYMMDD
+    pub fn as_u32(&self) -> u32 {
+        self.0
+    }
+
+    /// Completed years from this date to `date`; zero if `date` is earlier
+    pub fn years_until(&self, date: CivilDate) -> u32 {
+        date.0.saturating_sub(self.0) / 10_000
+    }
+}
+
+impl TryFrom<u32> for CivilDate {
+    type Error = CertificateError;
+
+    fn try_from(yyyymmdd: u32) -> Result<Self, Self::Error> {
+        CivilDate::new(yyyymmdd / 10_000, yyyymmdd / 100 % 100, yyyymmdd % 100)
+    }
+}
+
+impl fmt::Display for CivilDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.0 / 10_000, self.0 / 100 % 100, self.0 % 100)
+    }
+}
+
+/// `YYYY-MM-DD`
+impl FromStr for CivilDate {
+    type Err = CertificateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CertificateError::InvalidDate(s.to_string());
+        let mut parts = s.trim().splitn(3, '-').map(|p| p.parse::<u32>().map_err(|_| invalid()));
+        let (year, month, day) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(y), Some(m), Some(d)) => (y?, m?, d?),
+            _ => return Err(invalid()),
+        };
+        CivilDate::new(year, month, day)
+    }
+}
+
+impl TryFrom<String> for CivilDate {
+    type Error = CertificateError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<CivilDate> for String {
+    fn from(date: CivilDate) -> Self {
+        date.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CertificateAttributes {
+    pub jurisdiction: Jurisdiction,
+    pub accreditation: AccreditationLevel,
+    pub kyc_tier: KycTier,
+    pub date_of_birth: CivilDate,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComplianceCertificate {
+    pub version: u16,
+    /// Compliance authority that signs the certificate
+    pub issuer: [u8; 20],
+    /// Commitment of the certified user's deposit
+    pub subject: [u8; 32],
+    pub issued_at: u64,
+    pub expires_at: u64,
+    pub attributes: CertificateAttributes,
+}
+
+impl ComplianceCertificate {
+    /// A certificate in the current version
+    pub fn new(
+        issuer: [u8; 20],
+        subject: [u8; 32],
+        issued_at: u64,
+        expires_at: u64,
+        attributes: CertificateAttributes,
+    ) -> Result<Self, CertificateError> {
+        let certificate = Self { version: CERTIFICATE_VERSION, issuer, subject, issued_at, expires_at, attributes };
+        certificate.validate()?;
+        Ok(certificate)
+    }
+
+    fn validate(&self) -> Result<(), CertificateError> {
+        if self.version != CERTIFICATE_VERSION {
+            return Err(CertificateError::UnsupportedVersion(self.version));
+        }
+        if self.expires_at < self.issued_at {
+            return Err(CertificateError::InvalidValidity { issued_at: self.issued_at, expires_at: self.expires_at });
+        }
+        Ok(())
+    }
+
+    fn to_sol(&self) -> encoding::ComplianceCertificate {
+        encoding::ComplianceCertificate {
+            version: self.version,
+            issuer: self.issuer.into(),
+            subject: self.subject.into(),
+            issuedAt: self.issued_at,
+            expiresAt: self.expires_at,
+            jurisdiction: self.attributes.jurisdiction.as_bytes().into(),
+            accreditationLevel: self.attributes.accreditation as u8,
+            kycTier: self.attributes.kyc_tier as u8,
+            dateOfBirth: self.attributes.date_of_birth.as_u32(),
+        }
+    }
+
+    /// Canonical encoding: abi.encode(ComplianceCertificate)
+    pub fn encode(&self) -> Vec<u8> {
+        self.to_sol().abi_encode()
+    }
+
+    /// Parse a canonical encoding, rejecting unknown versions and attribute values
+    pub fn decode(data: &[u8]) -> Result<Self, CertificateError> {
+        let raw = encoding::ComplianceCertificate::abi_decode(data).map_err(|_| CertificateError::Malformed)?;
+        let jurisdiction: [u8; 2] = raw.jurisdiction.0;
+        let certificate = Self {
+            version: raw.version,
+            issuer: raw.issuer.into(),
+            subject: raw.subject.0,
+            issued_at: raw.issuedAt,
+            expires_at: raw.expiresAt,
+            attributes: CertificateAttributes {
+                jurisdiction: Jurisdiction::new(&String::from_utf8_lossy(&jurisdiction))?,
+                accreditation: AccreditationLevel::try_from(raw.accreditationLevel)?,
+                kyc_tier: KycTier::try_from(raw.kycTier)?,
+                date_of_birth: CivilDate::try_from(raw.dateOfBirth)?,
+            },
+        };
+        certificate.validate()?;
+        // Trailing or non-canonical bytes would give one certificate several encodings
+        if certificate.encode() != data {
+            return Err(CertificateError::Malformed);
+        }
+        Ok(certificate)
+    }
+
+    /// EIP-712 struct hash, the certificateHash in proofs
+    pub fn hash(&self) -> [u8; 32] {
+        self.to_sol().eip712_hash_struct().0
+    }
+
+    /// Digest the issuer signs: EIP-191 over the certificate hash
+    pub fn signing_digest(&self) -> [u8; 32] {
+        eip191_digest(&self.hash())
+    }
+
+    /// Check an (r, s, v) signature is by the certificate's issuer
+    pub fn verify_signature(&self, v: u8, r: &[u8; 32], s: &[u8; 32]) -> Result<(), CertificateError> {
+        match recover_signer(&self.signing_digest(), v, r, s) {
+            Some(signer) if signer == self.issuer => Ok(()),
+            _ => Err(CertificateError::InvalidSignature),
+        }
+    }
+
+    /// Whether `timestamp` falls within [issued_at, expires_at]
+    pub fn is_valid_at(&self, timestamp: u64) -> bool {
+        self.issued_at <= timestamp && timestamp <= self.expires_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signature::public_key_to_address;
+    use k256::ecdsa::SigningKey;
+
+    const ISSUED_AT: u64 = 1_700_000_000;
+    const EXPIRES_AT: u64 = 1_731_536_000;
+
+    fn certificate(issuer: [u8; 20]) -> ComplianceCertificate {
+        let attributes = CertificateAttributes {
+            jurisdiction: Jurisdiction::new("CH").unwrap(),
+            accreditation: AccreditationLevel::Accredited,
+            kyc_tier: KycTier::Standard,
+            date_of_birth: "1990-07-15".parse().unwrap(),
+        };
+        ComplianceCertificate::new(issuer, [0x22; 32], ISSUED_AT, EXPIRES_AT, attributes).unwrap()
+    }
+
+    #[test]
+    fn hash_matches_eip712_struct_hash() {
+        // ethers TypedDataEncoder.hashStruct("ComplianceCertificate", ...)
+        assert_eq!(
+            certificate([0x11; 20]).hash(),
+            alloy_primitives::b256!("fcadac2671941ee5fdd8cba36f5d978cbdd366c861c02cb6a8e0d551917927bb").0
+        );
+    }
+
+    #[test]
+    fn still_valid_in_the_second_it_expires() {
+        let cert = certificate([0x11; 20]);
+        assert!(cert.is_valid_at(EXPIRES_AT));
+        assert!(!cert.is_valid_at(EXPIRES_AT + 1));
+        assert!(!cert.is_valid_at(ISSUED_AT - 1));
+
+        // A certificate may be valid for a single second, but not for none
+        let mut instant = cert.clone();
+        instant.expires_at = ISSUED_AT;
+        assert!(instant.is_valid_at(ISSUED_AT));
+        assert_eq!(
+            ComplianceCertificate::new(cert.issuer, cert.subject, ISSUED_AT, ISSUED_AT - 1, cert.attributes),
+            Err(CertificateError::InvalidValidity { issued_at: ISSUED_AT, expires_at: ISSUED_AT - 1 })
+        );
+    }
+
+    #[test]
+    fn high_s_signature_is_rejected() {
+        let key = SigningKey::from_slice(&[7u8; 32]).unwrap();
+        let cert = certificate(public_key_to_address(key.verifying_key()));
+        let (signature, recovery_id) = key.sign_prehash_recoverable(&cert.signing_digest()).unwrap();
+        let r: [u8; 32] = signature.r().to_bytes().into();
+        let s: [u8; 32] = signature.s().to_bytes().into();
+        let v = 27 + recovery_id.to_byte();
+        assert_eq!(cert.verify_signature(v, &r, &s), Ok(()));
+
+        // (r, n - s) with the other recovery id recovers the same key, but is malleable
+        let high_s: [u8; 32] = (-*signature.s()).to_bytes().into();
+        assert_eq!(cert.verify_signature(v ^ 1, &r, &high_s), Err(CertificateError::InvalidSignature));
+    }
+
+    #[test]
+    fn decode_rejects_a_calendar_date_that_does_not_exist() {
+        let encoded = certificate([0x11; 20]).encode();
+        let mut leap = encoded.clone();
+        // dateOfBirth is the last word: 2023-02-29
+        leap[9 * 32 - 4..].copy_from_slice(&20_230_229u32.to_be_bytes());
+        assert!(matches!(ComplianceCertificate::decode(&leap), Err(CertificateError::InvalidDate(_))));
+
+        leap[9 * 32 - 4..].copy_from_slice(&20_240_229u32.to_be_bytes());
+        assert_eq!(
+            ComplianceCertificate::decode(&leap).unwrap().attributes.date_of_birth.to_string(),
+            "2024-02-29"
+        );
+
+        let mut trailing = encoded;
+        trailing.push(0);
+        assert_eq!(ComplianceCertificate::decode(&trailing), Err(CertificateError::Malformed));
+    }
+}
@@ -0,0 +1,395 @@
+//! JWT and W3C verifiable credentials
+//!
+//! KYC providers issue credentials as compact JWS tokens signed with ES256K
+//! (ECDSA over secp256k1 with SHA-256, RFC 8812). In credential mode the
+//! compliance circuit verifies the token against the issuer's public key, parses
+//! the claims and evaluates the venue's policy on them; only the issuer key's
+//! JWK thumbprint (RFC 7638) and the policy result are committed.
+//!
+//! The payload is a plain JWT or a W3C VC-JWT:
+//!
+//! ```json
+//! {
+//!   "iss": "did:example:kyc-provider",
+//!   "sub": "0x<deposit commitment>",
+//!   "nbf": 1700000000,
+//!   "exp": 1731536000,
+//!   "vc": {
+//!     "type": ["VerifiableCredential", "KycCredential"],
+//!     "credentialSubject": {
+//!       "jurisdiction": "CH",
+//!       "accreditation": "accredited",
+//!       "kycTier": "standard",
+//!       "dateOfBirth": "1990-05-17"
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! A plain JWT carries the `credentialSubject` attributes as top-level claims.
+//! `sub` binds the credential to the deposit commitment. `jurisdiction` and
+//! `dateOfBirth` are required; a missing `accreditation` or `kycTier` is the
+//! lowest level, so predicates on it fail closed.
+
+use crate::certificate::{AccreditationLevel, CertificateAttributes, KycTier};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use k256::ecdsa::signature::hazmat::PrehashVerifier;
+use k256::ecdsa::{Signature, VerifyingKey};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::str::FromStr;
+
+/// JWS `alg` accepted for credentials
+pub const JWS_ALGORITHM: &str = "ES256K";
+
+/// Longest token accepted, bounding the circuit's parsing cost
+pub const MAX_CREDENTIAL_LENGTH: usize = 8192;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredentialError {
+    TooLong(usize),
+    /// Not a compact JWS with JSON header and payload
+    Malformed,
+    UnsupportedAlgorithm(String),
+    /// The header lists critical extensions, none of which are supported
+    UnsupportedCritical,
+    InvalidIssuerKey,
+    InvalidSignature,
+    MissingClaim(&'static str),
+    InvalidClaim(&'static str),
+}
+
+impl fmt::Display for CredentialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CredentialError::TooLong(n) => {
+                write!(f, "credential is {} bytes, maximum is {}", n, MAX_CREDENTIAL_LENGTH)
+            }
+            CredentialError::Malformed => write!(f, "malformed compact JWS"),
+            CredentialError::UnsupportedAlgorithm(alg) => {
+                write!(f, "unsupported JWS algorithm {} (expected {})", alg, JWS_ALGORITHM)
+            }
+            CredentialError::UnsupportedCritical => write!(f, "unsupported critical JWS header parameters"),
+            CredentialError::InvalidIssuerKey => write!(f, "invalid secp256k1 issuer key"),
+            CredentialError::InvalidSignature => write!(f, "credential is not signed by the issuer key"),
+            CredentialError::MissingClaim(claim) => write!(f, "missing claim {}", claim),
+            CredentialError::InvalidClaim(claim) => write!(f, "invalid claim {}", claim),
+        }
+    }
+}
+
+impl std::error::Error for CredentialError {}
+
+/// Claims the compliance circuit uses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CredentialClaims {
+    /// `iss`, if present; the issuer is identified by its key
+    pub issuer: Option<String>,
+    /// Deposit commitment from `sub`
+    pub subject: [u8; 32],
+    pub not_before: Option<u64>,
+    pub expires_at: u64,
+    pub attributes: CertificateAttributes,
+}
+
+impl CredentialClaims {
+    /// Whether `timestamp` falls within [nbf, exp]
+    pub fn is_valid_at(&self, timestamp: u64) -> bool {
+        self.not_before.unwrap_or(0) <= timestamp && timestamp <= self.expires_at
+    }
+
+    fn from_json(payload: &Map<String, Value>) -> Result<Self, CredentialError> {
+        let issuer = match payload.get("iss") {
+            None => None,
+            Some(iss) => Some(iss.as_str().ok_or(CredentialError::InvalidClaim("iss"))?.to_string()),
+        };
+        let subject = payload.get("sub").ok_or(CredentialError::MissingClaim("sub"))?;
+        let subject = subject
+            .as_str()
+            .and_then(parse_bytes32)
+            .ok_or(CredentialError::InvalidClaim("sub"))?;
+        let expires_at = numeric_date(payload, "exp")?.ok_or(CredentialError::MissingClaim("exp"))?;
+        let not_before = numeric_date(payload, "nbf")?;
+
+        // VC-JWT attributes sit in vc.credentialSubject, plain JWT ones at the top level
+        let subject_claims = match payload.get("vc") {
+            Some(vc) => vc
+                .get("credentialSubject")
+                .ok_or(CredentialError::MissingClaim("vc.credentialSubject"))?
+                .as_object()
+                .ok_or(CredentialError::InvalidClaim("vc.credentialSubject"))?,
+            None => payload,
+        };
+        let attributes = CertificateAttributes {
+            jurisdiction: attribute(subject_claims, "jurisdiction")?
+                .ok_or(CredentialError::MissingClaim("jurisdiction"))?,
+            accreditation: attribute(subject_claims, "accreditation")?.unwrap_or(AccreditationLevel::None),
+            kyc_tier: attribute(subject_claims, "kycTier")?.unwrap_or(KycTier::None),
+            date_of_birth: attribute(subject_claims, "dateOfBirth")?
+                .ok_or(CredentialError::MissingClaim("dateOfBirth"))?,
+        };
+
+        Ok(Self { issuer, subject, not_before, expires_at, attributes })
+    }
+}
+
+/// A parsed ES256K compact JWS credential
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Credential {
+    pub claims: CredentialClaims,
+    /// SHA-256 of the JWS signing input, `header.payload`
+    digest: [u8; 32],
+    signature: [u8; 64],
+}
+
+impl Credential {
+    /// Parse a compact JWS; the signature is not checked
+    pub fn parse(token: &str) -> Result<Self, CredentialError> {
+        if token.len() > MAX_CREDENTIAL_LENGTH {
+            return Err(CredentialError::TooLong(token.len()));
+        }
+        let mut segments = token.split('.');
+        let (Some(header), Some(payload), Some(signature), None) =
+            (segments.next(), segments.next(), segments.next(), segments.next())
+        else {
+            return Err(CredentialError::Malformed);
+        };
+        let signing_input = &token[..header.len() + 1 + payload.len()];
+
+        let header = decode_object(header)?;
+        match header.get("alg").and_then(Value::as_str) {
+            Some(JWS_ALGORITHM) => {}
+            Some(alg) => return Err(CredentialError::UnsupportedAlgorithm(alg.to_string())),
+            None => return Err(CredentialError::Malformed),
+        }
+        if header.contains_key("crit") {
+            return Err(CredentialError::UnsupportedCritical);
+        }
+
+        let claims = CredentialClaims::from_json(&decode_object(payload)?)?;
+        let signature: [u8; 64] = decode_segment(signature)?
+            .try_into()
+            .map_err(|_| CredentialError::InvalidSignature)?;
+        let digest = Sha256::digest(signing_input.as_bytes()).into();
+
+        Ok(Self { claims, digest, signature })
+    }
+
+    /// Check the signature against a SEC1-encoded secp256k1 public key.
+    /// ES256K does not require low-s, so either form is accepted.
+    pub fn verify(&self, issuer_key: &[u8]) -> Result<(), CredentialError> {
+        let key = VerifyingKey::from_sec1_bytes(issuer_key).map_err(|_| CredentialError::InvalidIssuerKey)?;
+        let signature = Signature::from_slice(&self.signature).map_err(|_| CredentialError::InvalidSignature)?;
+        let signature = signature.normalize_s().unwrap_or(signature);
+        key.verify_prehash(&self.digest, &signature)
+            .map_err(|_| CredentialError::InvalidSignature)
+    }
+}
+
+/// RFC 7638 JWK thumbprint of a SEC1-encoded secp256k1 public key, the
+/// issuerKeyHash in proofs
+pub fn jwk_thumbprint(issuer_key: &[u8]) -> Result<[u8; 32], CredentialError> {
+    let key = VerifyingKey::from_sec1_bytes(issuer_key).map_err(|_| CredentialError::InvalidIssuerKey)?;
+    let point = key.to_encoded_point(false);
+    let (Some(x), Some(y)) = (point.x(), point.y()) else {
+        return Err(CredentialError::InvalidIssuerKey);
+    };
+    // Required members in lexicographic order, no whitespace
+    let jwk = format!(
+        r#"{{"crv":"secp256k1","kty":"EC","x":"{}","y":"{}"}}"#,
+        URL_SAFE_NO_PAD.encode(x),
+        URL_SAFE_NO_PAD.encode(y)
+    );
+    Ok(Sha256::digest(jwk.as_bytes()).into())
+}
+
+/// Uncompressed SEC1 public key from a secp256k1 JWK (`{"kty":"EC","crv":"secp256k1","x":..,"y":..}`)
+pub fn issuer_key_from_jwk(jwk: &str) -> Result<Vec<u8>, CredentialError> {
+    let jwk: Map<String, Value> = serde_json::from_str(jwk).map_err(|_| CredentialError::InvalidIssuerKey)?;
+    let member = |name: &str| jwk.get(name).and_then(Value::as_str).ok_or(CredentialError::InvalidIssuerKey);
+    if member("kty")? != "EC" || member("crv")? != "secp256k1" {
+        return Err(CredentialError::InvalidIssuerKey);
+    }
+    let x = decode_segment(member("x")?).map_err(|_| CredentialError::InvalidIssuerKey)?;
+    let y = decode_segment(member("y")?).map_err(|_| CredentialError::InvalidIssuerKey)?;
+    if x.len() != 32 || y.len() != 32 {
+        return Err(CredentialError::InvalidIssuerKey);
+    }
+
+    let mut key = Vec::with_capacity(65);
+    key.push(0x04);
+    key.extend_from_slice(&x);
+    key.extend_from_slice(&y);
+    // Reject points that are not on the curve
+    VerifyingKey::from_sec1_bytes(&key).map_err(|_| CredentialError::InvalidIssuerKey)?;
+    Ok(key)
+}
+
+fn decode_segment(segment: &str) -> Result<Vec<u8>, CredentialError> {
+    URL_SAFE_NO_PAD.decode(segment).map_err(|_| CredentialError::Malformed)
+}
+
+fn decode_object(segment: &str) -> Result<Map<String, Value>, CredentialError> {
+    serde_json::from_slice(&decode_segment(segment)?).map_err(|_| CredentialError::Malformed)
+}
+
+/// NumericDate claim in whole seconds
+fn numeric_date(claims: &Map<String, Value>, name: &'static str) -> Result<Option<u64>, CredentialError> {
+    match claims.get(name) {
+        None => Ok(None),
+        Some(value) => value.as_u64().map(Some).ok_or(CredentialError::InvalidClaim(name)),
+    }
+}
+
+fn attribute<T: FromStr>(claims: &Map<String, Value>, name: &'static str) -> Result<Option<T>, CredentialError> {
+    match claims.get(name) {
+        None => Ok(None),
+        Some(value) => value
+            .as_str()
+            .and_then(|s| s.parse().ok())
+            .map(Some)
+            .ok_or(CredentialError::InvalidClaim(name)),
+    }
+}
+
+fn parse_bytes32(s: &str) -> Option<[u8; 32]> {
+    let digits = s.strip_prefix("0x")?;
+    if digits.len() != 64 || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(digits.get(2 * i..2 * i + 2)?, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::SigningKey;
+    use serde_json::json;
+
+    fn issuer() -> SigningKey {
+        SigningKey::from_slice(&[0x4c; 32]).unwrap()
+    }
+
+    fn issuer_key(key: &SigningKey) -> Vec<u8> {
+        key.verifying_key().to_encoded_point(false).as_bytes().to_vec()
+    }
+
+    fn signing_input(header: &Value, payload: &Value) -> String {
+        format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(header.to_string()),
+            URL_SAFE_NO_PAD.encode(payload.to_string())
+        )
+    }
+
+    fn sign(key: &SigningKey, header: &Value, payload: &Value) -> String {
+        let input = signing_input(header, payload);
+        let (signature, _) = key.sign_prehash_recoverable(&Sha256::digest(input.as_bytes())).unwrap();
+        format!("{}.{}", input, URL_SAFE_NO_PAD.encode(signature.to_bytes()))
+    }
+
+    fn es256k() -> Value {
+        json!({ "alg": "ES256K", "typ": "JWT" })
+    }
+
+    fn claims() -> Value {
+        json!({
+            "sub": "0x4242424242424242424242424242424242424242424242424242424242424242",
+            "exp": 1_800_000_000u64,
+            "jurisdiction": "DE",
+            "dateOfBirth": "2001-12-31"
+        })
+    }
+
+    #[test]
+    fn high_s_signatures_are_accepted() {
+        let key = issuer();
+        let input = signing_input(&es256k(), &claims());
+        let (signature, _) = key.sign_prehash_recoverable(&Sha256::digest(input.as_bytes())).unwrap();
+        let high_s = Signature::from_scalars(signature.r().to_bytes(), (-*signature.s()).to_bytes()).unwrap();
+        assert!(high_s.normalize_s().is_some());
+
+        // ES256K leaves s unrestricted, unlike Ethereum signatures
+        let token = format!("{}.{}", input, URL_SAFE_NO_PAD.encode(high_s.to_bytes()));
+        let credential = Credential::parse(&token).unwrap();
+        assert_eq!(credential.verify(&issuer_key(&key)), Ok(()));
+        let other = SigningKey::from_slice(&[0x07; 32]).unwrap();
+        assert_eq!(credential.verify(&issuer_key(&other)), Err(CredentialError::InvalidSignature));
+    }
+
+    #[test]
+    fn valid_until_exp_inclusive_and_from_epoch_without_nbf() {
+        let credential = Credential::parse(&sign(&issuer(), &es256k(), &claims())).unwrap();
+        assert_eq!(credential.claims.not_before, None);
+        assert!(credential.claims.is_valid_at(0));
+        assert!(credential.claims.is_valid_at(1_800_000_000));
+        assert!(!credential.claims.is_valid_at(1_800_000_001));
+
+        // Fractional and negative NumericDates are rejected rather than rounded
+        let mut fractional = claims();
+        fractional["exp"] = json!(1.8e9 + 0.5);
+        assert_eq!(
+            Credential::parse(&sign(&issuer(), &es256k(), &fractional)).unwrap_err(),
+            CredentialError::InvalidClaim("exp")
+        );
+        let mut negative = claims();
+        negative["nbf"] = json!(-1);
+        assert_eq!(
+            Credential::parse(&sign(&issuer(), &es256k(), &negative)).unwrap_err(),
+            CredentialError::InvalidClaim("nbf")
+        );
+    }
+
+    #[test]
+    fn rejects_unsigned_and_oversized_tokens() {
+        let unsigned = format!("{}.", signing_input(&json!({ "alg": "none" }), &claims()));
+        assert_eq!(Credential::parse(&unsigned).unwrap_err(), CredentialError::UnsupportedAlgorithm("none".into()));
+        assert_eq!(
+            Credential::parse(&sign(&issuer(), &json!({ "alg": "ES256K", "crit": ["b64"], "b64": false }), &claims()))
+                .unwrap_err(),
+            CredentialError::UnsupportedCritical
+        );
+
+        // A trailing fourth segment is not compact JWS
+        let token = sign(&issuer(), &es256k(), &claims());
+        assert_eq!(Credential::parse(&format!("{}.", token)).unwrap_err(), CredentialError::Malformed);
+
+        let mut padded = claims();
+        padded["padding"] = json!("x".repeat(MAX_CREDENTIAL_LENGTH));
+        let long = sign(&issuer(), &es256k(), &padded);
+        assert_eq!(Credential::parse(&long).unwrap_err(), CredentialError::TooLong(long.len()));
+    }
+
+    #[test]
+    fn jwk_thumbprint_matches_rfc7638() {
+        // Thumbprint computed with Node's crypto over the canonical JWK
+        let jwk = r#"{
+            "kty": "EC",
+            "crv": "secp256k1",
+            "x": "TjuBr5wiNMrQnWec5gNe0TkjR85kzkBfXc02Iool3m4",
+            "y": "R_01xCFdHt9T5vg940RhXOcZvbD9h49u128G3Sd5Vt4"
+        }"#;
+        let key = issuer_key_from_jwk(jwk).unwrap();
+        assert_eq!(
+            jwk_thumbprint(&key).unwrap(),
+            alloy_primitives::b256!("fa49455f20afc12f65b644f4b8a44e0303e5a8d4a7a504e955a6aa1ec961cfec").0
+        );
+
+        // Compressed and uncompressed encodings of a key share a thumbprint
+        let compressed = issuer().verifying_key().to_encoded_point(true);
+        assert_eq!(jwk_thumbprint(compressed.as_bytes()), jwk_thumbprint(&issuer_key(&issuer())));
+
+        // x nudged off the curve
+        let off_curve = jwk.replace(
+            "TjuBr5wiNMrQnWec5gNe0TkjR85kzkBfXc02Iool3m4",
+            "TjuBr5wiNMrQnWec5gNe0TkjR85kzkBfXc02Iool3m5",
+        );
+        assert_eq!(issuer_key_from_jwk(&off_curve), Err(CredentialError::InvalidIssuerKey));
+    }
+}
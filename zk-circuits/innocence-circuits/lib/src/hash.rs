@@ -0,0 +1,113 @@
+//! Hash schemes
+//!
+//! Every commitment, nullifier hash and tree node in the circuits goes through the
+//! scheme selected at compile time with one of the lib's cargo features:
+//!
+//! - `keccak` (used when no other scheme is enabled): Keccak-256, matching the contracts and proof service
+//!   (`keccak256(abi.encode(secret, nullifier))`, `keccak256(nullifier)`).
+//!   Runs on SP1's keccak precompile through the patched `tiny-keccak`.
+//! - `sha256`: SHA-256, the original circuit encoding.
+//! - `poseidon2`: Poseidon2 over BabyBear with SP1's parameters, cheap to prove
+//!   recursively but not reproducible in Solidity.
+//!
+//! Switch schemes for the whole workspace in the root `Cargo.toml`, the only
+//! place a scheme is selected; the lib's default features select none.
+
+#[cfg(any(
+    all(feature = "keccak", feature = "sha256"),
+    all(feature = "keccak", feature = "poseidon2"),
+    all(feature = "sha256", feature = "poseidon2"),
+))]
+compile_error!("innocence-circuits-lib: the `keccak`, `sha256` and `poseidon2` features are mutually exclusive");
+
+/// A 32-byte hash function used for commitments and Merkle trees
+pub trait HashScheme {
+    const NAME: &'static str;
+
+    fn hash(data: &[u8]) -> [u8; 32];
+
+    /// Hash of two concatenated 32-byte values
+    fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let mut data = [0u8; 64];
+        data[..32].copy_from_slice(left);
+        data[32..].copy_from_slice(right);
+        Self::hash(&data)
+    }
+}
+
+/// SHA-256
+pub struct Sha256Hash;
+
+impl HashScheme for Sha256Hash {
+    const NAME: &'static str = "sha256";
+
+    fn hash(data: &[u8]) -> [u8; 32] {
+        use sha2::{Sha256, Digest};
+        Sha256::digest(data).into()
+    }
+}
+
+/// Keccak-256, as used by the EVM
+pub struct Keccak256Hash;
+
+impl HashScheme for Keccak256Hash {
+    const NAME: &'static str = "keccak256";
+
+    fn hash(data: &[u8]) -> [u8; 32] {
+        use tiny_keccak::{Hasher, Keccak};
+        let mut hasher = Keccak::v256();
+        let mut output = [0u8; 32];
+        hasher.update(data);
+        hasher.finalize(&mut output);
+        output
+    }
+}
+
+/// Poseidon2 sponge over BabyBear (width 16, rate 8), the permutation SP1 uses internally.
+/// Input bytes are packed 3 per field element behind a length prefix; the 8 output
+/// elements are serialized big-endian into 32 bytes.
+#[cfg(feature = "poseidon2")]
+pub struct Poseidon2Hash;
+
+#[cfg(feature = "poseidon2")]
+impl HashScheme for Poseidon2Hash {
+    const NAME: &'static str = "poseidon2";
+
+    fn hash(data: &[u8]) -> [u8; 32] {
+        use p3_baby_bear::BabyBear;
+        use p3_field::{AbstractField, PrimeField32};
+
+        let mut input = Vec::with_capacity(1 + data.len().div_ceil(3));
+        input.push(BabyBear::from_canonical_u32(data.len() as u32));
+        for chunk in data.chunks(3) {
+            let mut limb = [0u8; 4];
+            limb[1..1 + chunk.len()].copy_from_slice(chunk);
+            input.push(BabyBear::from_canonical_u32(u32::from_be_bytes(limb)));
+        }
+
+        let digest = sp1_primitives::poseidon2_hash(input);
+        let mut output = [0u8; 32];
+        for (i, element) in digest.iter().enumerate() {
+            output[i * 4..i * 4 + 4].copy_from_slice(&element.as_canonical_u32().to_be_bytes());
+        }
+        output
+    }
+}
+
+/// The scheme selected by the enabled cargo feature
+#[cfg(not(any(feature = "sha256", feature = "poseidon2")))]
+pub type ActiveHash = Keccak256Hash;
+#[cfg(feature = "sha256")]
+pub type ActiveHash = Sha256Hash;
+#[cfg(feature = "poseidon2")]
+pub type ActiveHash = Poseidon2Hash;
+
+/// Hash arbitrary bytes with the active scheme
+pub fn hash(data: &[u8]) -> [u8; 32] {
+    ActiveHash::hash(data)
+}
+
+/// Hash two nodes with the active scheme
+pub fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    ActiveHash::hash_pair(left, right)
+}
@@ -0,0 +1,272 @@
+//! Trusted compliance issuers
+//!
+//! A deployment can trust several compliance authorities. Their addresses, in
+//! ascending order, are the leaves of a fixed-depth Merkle tree (see `tree`), and
+//! the compliance circuit commits only the tree's root: a proof shows that the
+//! certificate was signed by some issuer in the set, not by which one.
+//!
+//! The host builds the set from a config file listing the issuers:
+//!
+//! ```toml
+//! [[issuers]]
+//! name = "Example KYC"
+//! address = "0x5Bd2F329C50860366c0E6D3b4227a422B66AD203"
+//! ```
+
+use crate::signature::parse_evm_address;
+use crate::{IncrementalMerkleTree, MerkleProof};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Depth of the issuer tree; every membership path has exactly this many levels
+pub const ISSUER_TREE_DEPTH: usize = 8;
+
+/// Issuers per set
+pub const MAX_ISSUERS: usize = 1 << ISSUER_TREE_DEPTH;
+
+const ISSUER_LEAF_DOMAIN: &[u8] = b"innocence-compliance-issuer";
+
+/// Hash an issuer address into a tree leaf
+pub fn compute_issuer_leaf(issuer: &[u8; 20]) -> [u8; 32] {
+    let mut data = Vec::with_capacity(ISSUER_LEAF_DOMAIN.len() + 20);
+    data.extend_from_slice(ISSUER_LEAF_DOMAIN);
+    data.extend_from_slice(issuer);
+    crate::hash::hash(&data)
+}
+
+/// Whether `proof` places `issuer` in the issuer set with root `root`
+pub fn verify_issuer(issuer: &[u8; 20], proof: &MerkleProof, root: &[u8; 32]) -> bool {
+    proof.depth() == ISSUER_TREE_DEPTH && proof.leaf == compute_issuer_leaf(issuer) && proof.verify(root).is_ok()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IssuerSetError {
+    Empty,
+    TooManyIssuers(usize),
+    InvalidAddress(String),
+    /// The zero address, which no key signs for
+    ZeroAddress,
+    DuplicateIssuer([u8; 20]),
+    Load(String),
+}
+
+impl fmt::Display for IssuerSetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IssuerSetError::Empty => write!(f, "issuer set is empty"),
+            IssuerSetError::TooManyIssuers(n) => write!(f, "{} issuers, maximum is {}", n, MAX_ISSUERS),
+            IssuerSetError::InvalidAddress(address) => write!(f, "invalid issuer address {}", address),
+            IssuerSetError::ZeroAddress => write!(f, "issuer address is zero"),
+            IssuerSetError::DuplicateIssuer(address) => {
+                write!(f, "issuer 0x")?;
+                address.iter().try_for_each(|b| write!(f, "{:02x}", b))?;
+                write!(f, " is listed twice")
+            }
+            IssuerSetError::Load(e) => write!(f, "invalid issuer set: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for IssuerSetError {}
+
+/// A trusted compliance authority
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issuer {
+    /// Display name, not part of the tree
+    pub name: String,
+    pub address: [u8; 20],
+}
+
+/// Validated issuer set and its Merkle tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "IssuerSetFile", into = "IssuerSetFile")]
+pub struct IssuerSet {
+    issuers: Vec<Issuer>,
+    tree: IncrementalMerkleTree,
+}
+
+impl IssuerSet {
+    /// Build the canonical set: issuers are ordered by address
+    pub fn new(mut issuers: Vec<Issuer>) -> Result<Self, IssuerSetError> {
+        if issuers.is_empty() {
+            return Err(IssuerSetError::Empty);
+        }
+        if issuers.len() > MAX_ISSUERS {
+            return Err(IssuerSetError::TooManyIssuers(issuers.len()));
+        }
+        issuers.sort_by_key(|issuer| issuer.address);
+        for (i, issuer) in issuers.iter().enumerate() {
+            if issuer.address == [0u8; 20] {
+                return Err(IssuerSetError::ZeroAddress);
+            }
+            if i > 0 && issuers[i - 1].address == issuer.address {
+                return Err(IssuerSetError::DuplicateIssuer(issuer.address));
+            }
+        }
+
+        let leaves: Vec<[u8; 32]> = issuers.iter().map(|issuer| compute_issuer_leaf(&issuer.address)).collect();
+        let tree = IncrementalMerkleTree::from_leaves(ISSUER_TREE_DEPTH, &leaves)
+            .expect("issuer count is within the tree capacity");
+        Ok(Self { issuers, tree })
+    }
+
+    /// Set of unnamed issuers
+    pub fn from_addresses(addresses: &[[u8; 20]]) -> Result<Self, IssuerSetError> {
+        Self::new(
+            addresses
+                .iter()
+                .map(|address| Issuer { name: String::new(), address: *address })
+                .collect(),
+        )
+    }
+
+    /// Issuers in address order
+    pub fn issuers(&self) -> &[Issuer] {
+        &self.issuers
+    }
+
+    pub fn len(&self) -> usize {
+        self.issuers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.issuers.is_empty()
+    }
+
+    pub fn contains(&self, address: &[u8; 20]) -> bool {
+        self.position(address).is_some()
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.tree.root()
+    }
+
+    /// Membership path for `address`, or None if it is not in the set
+    pub fn proof(&self, address: &[u8; 20]) -> Option<MerkleProof> {
+        let index = self.position(address)?;
+        self.tree.proof(index as u64).ok()
+    }
+
+    fn position(&self, address: &[u8; 20]) -> Option<usize> {
+        self.issuers.binary_search_by_key(address, |issuer| issuer.address).ok()
+    }
+}
+
+#[cfg(feature = "registry")]
+impl IssuerSet {
+    pub fn from_toml(data: &str) -> Result<Self, IssuerSetError> {
+        toml::from_str(data).map_err(|e| IssuerSetError::Load(e.to_string()))
+    }
+
+    pub fn from_json(data: &str) -> Result<Self, IssuerSetError> {
+        serde_json::from_str(data).map_err(|e| IssuerSetError::Load(e.to_string()))
+    }
+
+    /// Load a `.json` issuer set, or TOML for any other extension
+    pub fn load(path: &std::path::Path) -> Result<Self, IssuerSetError> {
+        let data = std::fs::read_to_string(path)
+            .map_err(|e| IssuerSetError::Load(format!("{}: {}", path.display(), e)))?;
+        if path.extension().is_some_and(|ext| ext == "json") {
+            Self::from_json(&data)
+        } else {
+            Self::from_toml(&data)
+        }
+    }
+}
+
+/// On-disk form of `IssuerSet`
+#[derive(Serialize, Deserialize)]
+struct IssuerSetFile {
+    issuers: Vec<IssuerEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IssuerEntry {
+    #[serde(default)]
+    name: String,
+    address: String,
+}
+
+impl From<IssuerSet> for IssuerSetFile {
+    fn from(set: IssuerSet) -> Self {
+        Self {
+            issuers: set
+                .issuers
+                .into_iter()
+                .map(|issuer| IssuerEntry {
+                    name: issuer.name,
+                    address: alloy_primitives::Address::from(issuer.address).to_checksum(None),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<IssuerSetFile> for IssuerSet {
+    type Error = IssuerSetError;
+
+    fn try_from(file: IssuerSetFile) -> Result<Self, Self::Error> {
+        let issuers = file
+            .issuers
+            .into_iter()
+            .map(|entry| {
+                let address = parse_evm_address(&entry.address)
+                    .ok_or_else(|| IssuerSetError::InvalidAddress(entry.address.clone()))?;
+                Ok(Issuer { name: entry.name, address })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        IssuerSet::new(issuers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(i: usize) -> [u8; 20] {
+        let mut address = [0u8; 20];
+        address[18..].copy_from_slice(&(i as u16 + 1).to_be_bytes());
+        address
+    }
+
+    #[test]
+    fn set_size_is_bounded_on_both_sides() {
+        assert_eq!(IssuerSet::from_addresses(&[]).unwrap_err(), IssuerSetError::Empty);
+
+        let full: Vec<[u8; 20]> = (0..MAX_ISSUERS).map(address).collect();
+        let set = IssuerSet::from_addresses(&full).unwrap();
+        let last = set.proof(&address(MAX_ISSUERS - 1)).unwrap();
+        assert!(verify_issuer(&address(MAX_ISSUERS - 1), &last, &set.root()));
+
+        let over: Vec<[u8; 20]> = (0..=MAX_ISSUERS).map(address).collect();
+        assert_eq!(IssuerSet::from_addresses(&over).unwrap_err(), IssuerSetError::TooManyIssuers(MAX_ISSUERS + 1));
+    }
+
+    #[test]
+    fn empty_slots_do_not_prove_the_zero_address() {
+        let set = IssuerSet::from_addresses(&[[0x11; 20]]).unwrap();
+        // The unfilled position next to the only issuer hashes to the root with a zero leaf
+        let mut empty_slot = set.proof(&[0x11; 20]).unwrap();
+        empty_slot.path[0] = empty_slot.leaf;
+        empty_slot.leaf = crate::tree::ZERO_VALUE;
+        empty_slot.leaf_index = 1;
+        assert!(empty_slot.verify(&set.root()).is_ok());
+        assert!(!verify_issuer(&[0u8; 20], &empty_slot, &set.root()));
+        assert_eq!(IssuerSet::from_addresses(&[[0u8; 20]]).unwrap_err(), IssuerSetError::ZeroAddress);
+    }
+
+    #[cfg(feature = "registry")]
+    #[test]
+    fn config_addresses_need_the_0x_prefix() {
+        let bare = "[[issuers]]\naddress = \"1111111111111111111111111111111111111111\"\n";
+        let err = IssuerSet::from_toml(bare).unwrap_err();
+        assert!(err.to_string().contains("invalid issuer address 1111"), "{}", err);
+
+        // Written back checksummed, and read back to the same root
+        let lowercase = "[[issuers]]\naddress = \"0x5bd2f329c50860366c0e6d3b4227a422b66ad203\"\n";
+        let set = IssuerSet::from_toml(lowercase).unwrap();
+        let json = serde_json::to_string(&set).unwrap();
+        assert!(json.contains("0x5Bd2F329C50860366c0E6D3b4227a422B66AD203"), "{}", json);
+        assert_eq!(IssuerSet::from_json(&json).unwrap().root(), set.root());
+    }
+}
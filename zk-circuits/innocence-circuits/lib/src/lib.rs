@@ -4,6 +4,45 @@
 
 use alloy_sol_types::sol;
 
+pub mod allowlist;
+pub mod amount;
+pub mod asset;
+pub mod certificate;
+#[cfg(feature = "credential")]
+pub mod credential;
+pub mod hash;
+pub mod issuers;
+pub mod margin;
+pub mod merkle;
+pub mod note;
+pub mod oracle;
+pub mod policy;
+pub mod revocation;
+pub mod sanctions;
+pub mod signature;
+pub mod sorted_tree;
+pub mod tree;
+
+pub use allowlist::{AllowlistTree, AllowlistedAddress, InnocenceMode};
+pub use amount::{format_units, parse_units, AmountError, U256};
+pub use asset::{Asset, AssetId, AssetRegistry, RegistryError};
+pub use certificate::{
+    AccreditationLevel, CertificateAttributes, CertificateError, CivilDate, ComplianceCertificate, ComplianceMode,
+    Jurisdiction, KycTier,
+};
+#[cfg(feature = "credential")]
+pub use credential::{Credential, CredentialClaims, CredentialError};
+pub use hash::{hash_pair, HashScheme};
+pub use issuers::{Issuer, IssuerSet, IssuerSetError};
+pub use margin::{MarginAccount, MarginError, MarginHealth, Position, Side};
+pub use merkle::{MerkleError, MerkleProof, MAX_MERKLE_DEPTH};
+pub use note::{note_public_key, Note};
+pub use oracle::{OracleError, PriceAttestation, SignedPrice};
+pub use policy::{Policy, PolicyError, Predicate};
+pub use revocation::{RevocationProof, RevocationTree};
+pub use sanctions::{NonMembershipProof, SanctionsTree};
+pub use tree::{IncrementalMerkleTree, TreeError};
+
 // Ownership proof public values
 sol! {
     struct OwnershipProofPublicValues {
@@ -22,7 +61,7 @@ sol! {
     }
 }
 
-// Trade proof public values
+// Trade proof public values (legacy layout - the trade circuit commits SwapProofPublicValues)
 sol! {
     struct TradeProofPublicValues {
         bytes32 commitment;
@@ -33,72 +72,166 @@ sol! {
     }
 }
 
-// Compliance proof public values (DEPRECATED - use InnocenceProof instead)
+// Swap proof public values - must match PrivateDEXExtension.SwapProofPublicValues
+sol! {
+    struct SwapProofPublicValues {
+        bytes32 commitment;
+        bytes32 nullifierHash;
+        uint64 tokenIn;
+        uint64 tokenOut;
+        uint256 amountIn;
+        uint256 minAmountOut;
+        bytes32 merkleRoot;
+    }
+}
+
+// Note-based swap proof public values: SwapProofPublicValues followed by the deposit
+// tree leaves of the fresh output notes and the price oracle terms, so PrivateDEXExtension can
+// still decode the prefix. priceTimestamp lets the verifier reject stale prices.
+sol! {
+    struct SwapNoteProofPublicValues {
+        bytes32 commitment;
+        bytes32 nullifierHash;
+        uint64 tokenIn;
+        uint64 tokenOut;
+        uint256 amountIn;
+        uint256 minAmountOut;
+        bytes32 merkleRoot;
+        bytes32 changeLeaf;
+        bytes32 outputLeaf;
+        address oracle;
+        uint64 priceTimestamp;
+        uint32 maxSlippageBps;
+    }
+}
+
+// Transfer proof public values - 2-in/2-out join-split inside the pool; the outputs
+// are deposit tree leaves
+sol! {
+    struct TransferProofPublicValues {
+        bytes32 merkleRoot;
+        bytes32 inputNullifierHash1;
+        bytes32 inputNullifierHash2;
+        bytes32 outputLeaf1;
+        bytes32 outputLeaf2;
+    }
+}
+
+// Margin proof public values - an account meets maintenance margin at these mark prices.
+// Mark prices are collateral base units per base unit of the asset, scaled by 1e18.
+// accountCommitment is a leaf of the venue's accounts tree under accountsRoot; the
+// verifier must check accountsRoot against the root it keeps for open accounts.
+sol! {
+    #[derive(Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    struct MarkPrice {
+        uint64 assetId;
+        uint256 price;
+        uint32 maintenanceMarginBps;
+    }
+
+    struct MarginProofPublicValues {
+        bytes32 accountCommitment;
+        bytes32 accountsRoot;
+        uint64 collateralAsset;
+        MarkPrice[] markPrices;
+    }
+}
+
+// Compliance proof public values (DEPRECATED - use InnocenceProof instead).
+// mode is the ComplianceMode, which fixes the layout of the rest: this struct in
+// certificate mode, CredentialProofPublicValues in credential mode.
+// issuerSetRoot is the set of trusted issuers, one of which signed the certificate.
+// revocationRoot is the revocation list the certificate was checked against.
+// predicateHash is Policy::hash of the policy checked; predicatesSatisfied is its result.
+// timestamp is the time expiry and minAge were checked against. The prover picks it,
+// so the verifier must require it to be at most block.timestamp and recent.
 sol! {
     struct ComplianceProofPublicValues {
+        uint8 mode;
         bytes32 commitment;
-        address complianceAuthority;
+        bytes32 issuerSetRoot;
         uint256 validUntil;
+        uint64 timestamp;
         bytes32 certificateHash;
+        bytes32 revocationRoot;
+        bytes32 predicateHash;
+        bool predicatesSatisfied;
     }
 }
 
-// Innocence proof public values - proves address is not sanctioned
+// Compliance proof public values in credential mode (ES256K JWT / VC-JWT).
+// issuerKeyHash is the RFC 7638 JWK thumbprint of the issuer's key; timestamp is
+// the time the credential's nbf and exp were checked against, bounded by the verifier
+// as in certificate mode.
+sol! {
+    struct CredentialProofPublicValues {
+        uint8 mode;
+        bytes32 commitment;
+        bytes32 issuerKeyHash;
+        uint64 timestamp;
+        bytes32 predicateHash;
+        bool predicatesSatisfied;
+    }
+}
+
+// Innocence proof public values - proves address is not sanctioned, or is allowlisted.
+// listRoot is the sanctions tree root or the allowlist root, as selected by mode
+// (the InnocenceMode). mode comes first, as in the compliance proof, so a verifier
+// built for the sanctions-only layout rejects both modes instead of misreading them.
 sol! {
     struct InnocenceProofPublicValues {
+        uint8 mode;
         address depositor;
-        bytes32 sanctionsRoot;
+        bytes32 listRoot;
         uint256 timestamp;
         bool isInnocent;
     }
 }
 
-// Helper functions for commitment generation
+// Withdraw proof public values - spends a deposit note to a bound recipient
+sol! {
+    struct WithdrawProofPublicValues {
+        bytes32 merkleRoot;
+        bytes32 nullifierHash;
+        address recipient;
+        address relayer;
+        uint256 fee;
+        uint256 amount;
+        uint64 assetId;
+    }
+}
+
+// Helper functions for commitment generation, all routed through the active hash scheme.
+// With `keccak` these match the contracts: keccak256(abi.encode(secret, nullifier))
 pub fn compute_commitment(secret: &[u8; 32], nullifier: &[u8; 32]) -> [u8; 32] {
-    use sha2::{Sha256, Digest};
-    let mut hasher = Sha256::new();
-    hasher.update(secret);
-    hasher.update(nullifier);
-    hasher.finalize().into()
+    hash_pair(secret, nullifier)
 }
 
+/// keccak256(nullifier) with the `keccak` scheme
 pub fn compute_nullifier_hash(nullifier: &[u8; 32]) -> [u8; 32] {
-    use sha2::{Sha256, Digest};
-    let mut hasher = Sha256::new();
-    hasher.update(nullifier);
-    hasher.finalize().into()
+    hash::hash(nullifier)
 }
 
-// Merkle tree helpers
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct MerkleProof {
-    pub leaf: [u8; 32],
-    pub path: Vec<[u8; 32]>,
-    pub indices: Vec<bool>, // false = left, true = right
+/// Hiding commitment to a note amount: hash(abi.encodePacked(uint256 amount, bytes32 amount_blinding)).
+/// The note commitment is public once deposited, so without the blinding a leaf's
+/// amount could be recovered by trying every value.
+pub fn compute_amount_commitment(amount: U256, amount_blinding: &[u8; 32]) -> [u8; 32] {
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(&amount.to_be_bytes::<32>());
+    data[32..].copy_from_slice(amount_blinding);
+    hash::hash(&data)
 }
 
-impl MerkleProof {
-    pub fn verify(&self, root: &[u8; 32]) -> bool {
-        use sha2::{Sha256, Digest};
-        
-        let mut current = self.leaf;
-        
-        for (i, sibling) in self.path.iter().enumerate() {
-            let mut hasher = Sha256::new();
-            
-            if self.indices[i] {
-                // Current node is on the right
-                hasher.update(sibling);
-                hasher.update(&current);
-            } else {
-                // Current node is on the left
-                hasher.update(&current);
-                hasher.update(sibling);
-            }
-            
-            current = hasher.finalize().into();
-        }
-        
-        &current == root
-    }
-}
\ No newline at end of file
+/// Deposit tree leaf for a note holding `amount` of `asset_id` under `commitment`:
+/// hash(abi.encodePacked(commitment, uint64 asset_id, compute_amount_commitment(amount, amount_blinding)))
+///
+/// This is the only leaf format in the deposit tree: deposits, trade and transfer
+/// outputs all insert it and every circuit checks membership of it. The contracts
+/// compute it for deposits from the deposited token, amount and amount blinding.
+pub fn compute_note_leaf(commitment: &[u8; 32], asset_id: u64, amount: U256, amount_blinding: &[u8; 32]) -> [u8; 32] {
+    let mut data = [0u8; 72];
+    data[..32].copy_from_slice(commitment);
+    data[32..40].copy_from_slice(&asset_id.to_be_bytes());
+    data[40..].copy_from_slice(&compute_amount_commitment(amount, amount_blinding));
+    hash::hash(&data)
+}
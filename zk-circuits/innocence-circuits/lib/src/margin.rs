@@ -0,0 +1,199 @@
+//! Perpetuals margin accounts
+//!
+//! A margin account is a private note of perpetual positions backed by a
+//! collateral balance. Only its commitment is public; the margin circuit proves the
+//! account's equity covers the maintenance margin at public mark prices. The venue
+//! records each account commitment as a leaf of its accounts tree when it opens or
+//! updates the account, and the circuit proves membership under that tree's root,
+//! so a prover cannot present an account with collateral it never posted.
+//!
+//! Prices use the oracle convention: collateral base units per base unit of the
+//! position asset, scaled by `PRICE_SCALE` (1e18). Sizes are in base units of the
+//! position asset. Health is compared exactly in collateral base units scaled by
+//! `PRICE_SCALE * 10000`, so host and guest never disagree on rounding.
+
+use crate::oracle::PRICE_SCALE;
+use crate::{hash, MarkPrice, U256};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Domain separator for account commitments, so they never collide with note commitments
+const MARGIN_ACCOUNT_DOMAIN: &[u8] = b"innocence-margin-account";
+
+/// Positions per account, bounding the circuit's cycle count
+pub const MAX_POSITIONS: usize = 16;
+
+/// Margin rates are given in basis points
+pub const MAX_MARGIN_BPS: u32 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarginError {
+    TooManyPositions(usize),
+    /// No mark price for an asset the account holds a position in
+    MissingMarkPrice(u64),
+    DuplicateMarkPrice(u64),
+    InvalidMarginBps { asset_id: u64, bps: u32 },
+    /// Intermediate product does not fit in 256 bits
+    Overflow,
+}
+
+impl fmt::Display for MarginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MarginError::TooManyPositions(n) => {
+                write!(f, "{} positions, maximum is {}", n, MAX_POSITIONS)
+            }
+            MarginError::MissingMarkPrice(asset_id) => write!(f, "no mark price for asset {}", asset_id),
+            MarginError::DuplicateMarkPrice(asset_id) => {
+                write!(f, "asset {} has more than one mark price", asset_id)
+            }
+            MarginError::InvalidMarginBps { asset_id, bps } => {
+                write!(f, "asset {} margin {} bps exceeds {} bps", asset_id, bps, MAX_MARGIN_BPS)
+            }
+            MarginError::Overflow => write!(f, "margin computation overflows 256 bits"),
+        }
+    }
+}
+
+impl std::error::Error for MarginError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Long = 0,
+    Short = 1,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Position {
+    pub asset_id: u64,
+    pub side: Side,
+    /// Base units of the position asset
+    pub size: U256,
+    /// Fixed-point price the position was opened at
+    pub entry_price: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarginAccount {
+    /// Owner's public note key
+    pub owner: [u8; 32],
+    pub collateral_asset: u64,
+    /// Base units of the collateral asset
+    pub collateral: U256,
+    pub positions: Vec<Position>,
+    pub blinding: [u8; 32],
+}
+
+impl MarginAccount {
+    /// hash("innocence-margin-account" || owner || uint64 collateral_asset || uint256 collateral || blinding
+    ///      || for each position: uint64 asset_id || uint8 side (0 long, 1 short) || uint256 size || uint256 entry_price)
+    pub fn commitment(&self) -> [u8; 32] {
+        let mut data = Vec::with_capacity(MARGIN_ACCOUNT_DOMAIN.len() + 104 + 73 * self.positions.len());
+        data.extend_from_slice(MARGIN_ACCOUNT_DOMAIN);
+        data.extend_from_slice(&self.owner);
+        data.extend_from_slice(&self.collateral_asset.to_be_bytes());
+        data.extend_from_slice(&self.collateral.to_be_bytes::<32>());
+        data.extend_from_slice(&self.blinding);
+        for position in &self.positions {
+            data.extend_from_slice(&position.asset_id.to_be_bytes());
+            data.push(position.side as u8);
+            data.extend_from_slice(&position.size.to_be_bytes::<32>());
+            data.extend_from_slice(&position.entry_price.to_be_bytes::<32>());
+        }
+        hash::hash(&data)
+    }
+
+    /// Whether `spending_key` controls this account
+    pub fn is_owned_by(&self, spending_key: &[u8; 32]) -> bool {
+        self.owner == crate::note_public_key(spending_key)
+    }
+
+    /// Equity and maintenance requirement at `mark_prices`
+    pub fn health(&self, mark_prices: &[MarkPrice]) -> Result<MarginHealth, MarginError> {
+        if self.positions.len() > MAX_POSITIONS {
+            return Err(MarginError::TooManyPositions(self.positions.len()));
+        }
+        for (i, mark) in mark_prices.iter().enumerate() {
+            if mark_prices[..i].iter().any(|m| m.assetId == mark.assetId) {
+                return Err(MarginError::DuplicateMarkPrice(mark.assetId));
+            }
+            if mark.maintenanceMarginBps > MAX_MARGIN_BPS {
+                return Err(MarginError::InvalidMarginBps { asset_id: mark.assetId, bps: mark.maintenanceMarginBps });
+            }
+        }
+
+        let bps = U256::from(MAX_MARGIN_BPS);
+        let mut health = MarginHealth {
+            assets: mul(mul(self.collateral, PRICE_SCALE)?, bps)?,
+            liabilities: U256::ZERO,
+            requirement: U256::ZERO,
+        };
+        for position in &self.positions {
+            let mark = mark_prices
+                .iter()
+                .find(|m| m.assetId == position.asset_id)
+                .ok_or(MarginError::MissingMarkPrice(position.asset_id))?;
+
+            // Unrealised PnL: size * |mark - entry|, a gain when the price moved the position's way
+            let gain = match position.side {
+                Side::Long => mark.price >= position.entry_price,
+                Side::Short => mark.price <= position.entry_price,
+            };
+            let pnl = mul(mul(position.size, mark.price.abs_diff(position.entry_price))?, bps)?;
+            if gain {
+                health.assets = add(health.assets, pnl)?;
+            } else {
+                health.liabilities = add(health.liabilities, pnl)?;
+            }
+
+            // Maintenance margin on the notional at the mark price
+            let notional = mul(position.size, mark.price)?;
+            let margin = mul(notional, U256::from(mark.maintenanceMarginBps))?;
+            health.requirement = add(health.requirement, margin)?;
+        }
+        Ok(health)
+    }
+}
+
+/// Margin health in collateral base units scaled by `PRICE_SCALE * 10000`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarginHealth {
+    /// Collateral plus unrealised gains
+    pub assets: U256,
+    /// Unrealised losses
+    pub liabilities: U256,
+    /// Maintenance margin over all positions
+    pub requirement: U256,
+}
+
+impl MarginHealth {
+    /// Whether equity covers the maintenance margin
+    pub fn is_healthy(&self) -> bool {
+        self.assets
+            .checked_sub(self.liabilities)
+            .is_some_and(|equity| equity >= self.requirement)
+    }
+
+    /// Equity in collateral base units, rounded down; `None` when losses exceed collateral
+    pub fn equity(&self) -> Option<U256> {
+        self.assets.checked_sub(self.liabilities).map(|equity| equity / scale())
+    }
+
+    /// Maintenance margin in collateral base units, rounded up
+    pub fn maintenance_margin(&self) -> U256 {
+        self.requirement.div_ceil(scale())
+    }
+}
+
+fn scale() -> U256 {
+    PRICE_SCALE * U256::from(MAX_MARGIN_BPS)
+}
+
+fn mul(a: U256, b: U256) -> Result<U256, MarginError> {
+    a.checked_mul(b).ok_or(MarginError::Overflow)
+}
+
+fn add(a: U256, b: U256) -> Result<U256, MarginError> {
+    a.checked_add(b).ok_or(MarginError::Overflow)
+}
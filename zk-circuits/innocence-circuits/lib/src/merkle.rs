@@ -0,0 +1,88 @@
+//! Merkle inclusion proofs
+//!
+//! A proof is the leaf, its sibling path from the bottom layer up, and the leaf's
+//! position packed into a `u64`: bit `i` set means the node at level `i` is a right
+//! child. Proofs are bounded to `MAX_MERKLE_DEPTH` levels so a witness cannot force
+//! the guest into unbounded hashing.
+
+use crate::hash_pair;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Deepest Merkle path accepted by `MerkleProof::verify`
+pub const MAX_MERKLE_DEPTH: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MerkleError {
+    /// Path is longer than `MAX_MERKLE_DEPTH`
+    DepthTooLarge { depth: usize },
+    /// Leaf index does not fit in a tree of the path's depth
+    IndexOutOfRange { index: u64, depth: usize },
+    /// Path hashes to a different root
+    RootMismatch,
+}
+
+impl fmt::Display for MerkleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MerkleError::DepthTooLarge { depth } => {
+                write!(f, "path depth {} exceeds maximum {}", depth, MAX_MERKLE_DEPTH)
+            }
+            MerkleError::IndexOutOfRange { index, depth } => {
+                write!(f, "leaf index {} out of range for depth {}", index, depth)
+            }
+            MerkleError::RootMismatch => write!(f, "path does not hash to the root"),
+        }
+    }
+}
+
+impl std::error::Error for MerkleError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    pub leaf: [u8; 32],
+    pub path: Vec<[u8; 32]>,
+    pub leaf_index: u64, // bit i: 0 = left, 1 = right at level i
+}
+
+impl MerkleProof {
+    pub fn depth(&self) -> usize {
+        self.path.len()
+    }
+
+    /// Check the path depth and that the index has no bits above it
+    pub fn check_shape(&self) -> Result<(), MerkleError> {
+        let depth = self.depth();
+        if depth > MAX_MERKLE_DEPTH {
+            return Err(MerkleError::DepthTooLarge { depth });
+        }
+        if self.leaf_index >> depth != 0 {
+            return Err(MerkleError::IndexOutOfRange { index: self.leaf_index, depth });
+        }
+        Ok(())
+    }
+
+    /// Root the path hashes to
+    pub fn compute_root(&self) -> Result<[u8; 32], MerkleError> {
+        self.check_shape()?;
+
+        let mut current = self.leaf;
+        for (level, sibling) in self.path.iter().enumerate() {
+            current = if (self.leaf_index >> level) & 1 == 1 {
+                // Current node is on the right
+                hash_pair(sibling, &current)
+            } else {
+                // Current node is on the left
+                hash_pair(&current, sibling)
+            };
+        }
+        Ok(current)
+    }
+
+    pub fn verify(&self, root: &[u8; 32]) -> Result<(), MerkleError> {
+        if self.compute_root()? != *root {
+            return Err(MerkleError::RootMismatch);
+        }
+        Ok(())
+    }
+}
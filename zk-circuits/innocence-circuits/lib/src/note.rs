@@ -0,0 +1,104 @@
+//! Notes
+//!
+//! A note is a private UTXO: an amount of one asset owned by the holder of a
+//! spending key. Deposits are notes too. The note commitment,
+//! `compute_commitment(secret, blinding)`, is what a depositor registers and
+//! compliance certificates name; the deposit tree holds the note leaf,
+//! `compute_note_leaf(commitment, asset_id, amount, amount_blinding)`, so every
+//! circuit spends and proves balances against the same leaves. The amount
+//! blinding keeps the leaf from revealing the amount to anyone who knows the
+//! commitment. Spending a note reveals its nullifier
+//! hash, which the contracts record to stop the same note being spent twice.
+//!
+//! A note's `secret` is its owner's public note key, the hash of a spending key.
+//! Spending circuits require the spending key, so a sender who builds a note for a
+//! recipient knows every field of it but still cannot spend it. The nullifier hash
+//! is not stored in the note: circuits derive it from the spending key, the
+//! commitment and the leaf's position in the deposit tree, so the sender can
+//! neither predict it to watch for the spend nor pick one that collides with
+//! another note's.
+
+use crate::{compute_commitment, compute_note_leaf, hash, U256};
+use serde::{Deserialize, Serialize};
+
+/// Domain separator for note keys, so a public note key is never a commitment preimage
+const NOTE_KEY_DOMAIN: &[u8] = b"innocence-note-key";
+
+/// Domain separator for note nullifier hashes
+const NOTE_NULLIFIER_DOMAIN: &[u8] = b"innocence-note-nullifier";
+
+/// Public note key for a spending key; notes owned by it carry it as their `secret`
+pub fn note_public_key(spending_key: &[u8; 32]) -> [u8; 32] {
+    let mut data = Vec::with_capacity(NOTE_KEY_DOMAIN.len() + 32);
+    data.extend_from_slice(NOTE_KEY_DOMAIN);
+    data.extend_from_slice(spending_key);
+    hash::hash(&data)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Note {
+    pub asset_id: u64,
+    pub amount: U256,
+    pub secret: [u8; 32],
+    pub blinding: [u8; 32],
+    pub amount_blinding: [u8; 32],
+}
+
+impl Note {
+    /// hash(secret || blinding), the commitment a deposit registers
+    pub fn commitment(&self) -> [u8; 32] {
+        compute_commitment(&self.secret, &self.blinding)
+    }
+
+    /// Deposit tree leaf of this note
+    pub fn leaf(&self) -> [u8; 32] {
+        compute_note_leaf(&self.commitment(), self.asset_id, self.amount, &self.amount_blinding)
+    }
+
+    /// Whether `spending_key` may spend this note
+    pub fn is_owned_by(&self, spending_key: &[u8; 32]) -> bool {
+        self.secret == note_public_key(spending_key)
+    }
+
+    /// Revealed when the note at `leaf_index` is spent:
+    /// hash("innocence-note-nullifier" || spending_key || commitment || uint64 leaf_index)
+    ///
+    /// Including the position gives a note re-created with the same fields its own
+    /// nullifier, so it cannot block the spend of the first one.
+    pub fn nullifier_hash(&self, spending_key: &[u8; 32], leaf_index: u64) -> [u8; 32] {
+        let mut data = Vec::with_capacity(NOTE_NULLIFIER_DOMAIN.len() + 72);
+        data.extend_from_slice(NOTE_NULLIFIER_DOMAIN);
+        data.extend_from_slice(spending_key);
+        data.extend_from_slice(&self.commitment());
+        data.extend_from_slice(&leaf_index.to_be_bytes());
+        hash::hash(&data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(amount_blinding: [u8; 32]) -> Note {
+        Note {
+            asset_id: 7,
+            amount: U256::from(1000u64),
+            secret: [1u8; 32],
+            blinding: [2u8; 32],
+            amount_blinding,
+        }
+    }
+
+    #[test]
+    fn amount_blinding_hides_the_amount() {
+        let (a, b) = (note([3u8; 32]), note([4u8; 32]));
+
+        // Same commitment, asset and amount, different leaves
+        assert_eq!(a.commitment(), b.commitment());
+        assert_ne!(a.leaf(), b.leaf());
+
+        // Knowing the commitment and trying amounts does not find the leaf
+        let guessed = compute_note_leaf(&a.commitment(), a.asset_id, a.amount, &[0u8; 32]);
+        assert_ne!(guessed, a.leaf());
+    }
+}
@@ -0,0 +1,140 @@
+//! Oracle price attestations
+//!
+//! An oracle signs the price of a base asset in a quote asset at a point in time.
+//! The trade circuit checks the signature against the oracle address given as a
+//! public input, then bounds the swap's guaranteed output by the attested price
+//! less the allowed slippage.
+//!
+//! `price` is quote base units per base unit scaled by `PRICE_SCALE` (1e18), so it
+//! does not depend on either asset's decimals. The oracle signs the EIP-712 struct
+//! hash of `PriceAttestation` with `personal_sign` (EIP-191), as compliance
+//! authorities sign certificates.
+
+use crate::amount::U256;
+use crate::signature::{eip191_digest, recover_signer};
+use alloy_sol_types::{sol, SolStruct};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+sol! {
+    struct PriceAttestation {
+        uint64 baseAsset;
+        uint64 quoteAsset;
+        uint256 price;
+        uint64 timestamp;
+    }
+}
+
+/// Decimals of the fixed-point `price`
+pub const PRICE_DECIMALS: u8 = 18;
+
+/// 10^PRICE_DECIMALS
+pub const PRICE_SCALE: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+
+/// Slippage is given in basis points; 10000 accepts any output
+pub const MAX_SLIPPAGE_BPS: u32 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OracleError {
+    /// Signature is malformed or does not recover to the stated signer
+    InvalidSignature,
+    /// Attestation is signed by someone other than the expected oracle
+    UntrustedSigner { signer: [u8; 20] },
+    SlippageTooLarge(u32),
+    /// Intermediate product does not fit in 256 bits
+    Overflow,
+}
+
+impl fmt::Display for OracleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OracleError::InvalidSignature => write!(f, "invalid price attestation signature"),
+            OracleError::UntrustedSigner { signer } => {
+                write!(f, "price attestation signed by untrusted key 0x")?;
+                signer.iter().try_for_each(|b| write!(f, "{:02x}", b))
+            }
+            OracleError::SlippageTooLarge(bps) => {
+                write!(f, "slippage {} bps exceeds {} bps", bps, MAX_SLIPPAGE_BPS)
+            }
+            OracleError::Overflow => write!(f, "price computation overflows 256 bits"),
+        }
+    }
+}
+
+impl std::error::Error for OracleError {}
+
+/// A price attestation with the oracle's signature, as the trade circuit reads it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedPrice {
+    pub base_asset: u64,
+    pub quote_asset: u64,
+    pub price: U256,
+    pub timestamp: u64,
+    /// Address the attestation claims to be signed by
+    pub signer: [u8; 20],
+    pub v: u8,
+    pub r: [u8; 32],
+    pub s: [u8; 32],
+}
+
+impl SignedPrice {
+    pub fn attestation(&self) -> PriceAttestation {
+        PriceAttestation {
+            baseAsset: self.base_asset,
+            quoteAsset: self.quote_asset,
+            price: self.price,
+            timestamp: self.timestamp,
+        }
+    }
+
+    /// Check the signature recovers to `signer` and that `signer` is `oracle`
+    pub fn verify(&self, oracle: &[u8; 20]) -> Result<(), OracleError> {
+        let recovered = recover_signer(&attestation_digest(&self.attestation()), self.v, &self.r, &self.s)
+            .ok_or(OracleError::InvalidSignature)?;
+        if recovered != self.signer {
+            return Err(OracleError::InvalidSignature);
+        }
+        if self.signer != *oracle {
+            return Err(OracleError::UntrustedSigner { signer: self.signer });
+        }
+        Ok(())
+    }
+}
+
+/// Digest the oracle signs: EIP-191 over the EIP-712 struct hash
+pub fn attestation_digest(attestation: &PriceAttestation) -> [u8; 32] {
+    eip191_digest(&attestation.eip712_hash_struct().0)
+}
+
+/// from_amount * price * (10000 - max_slippage_bps), the bound scaled by PRICE_SCALE * 10000
+fn scaled_min_output(from_amount: U256, price: U256, max_slippage_bps: u32) -> Result<U256, OracleError> {
+    if max_slippage_bps > MAX_SLIPPAGE_BPS {
+        return Err(OracleError::SlippageTooLarge(max_slippage_bps));
+    }
+    from_amount
+        .checked_mul(price)
+        .and_then(|v| v.checked_mul(U256::from(MAX_SLIPPAGE_BPS - max_slippage_bps)))
+        .ok_or(OracleError::Overflow)
+}
+
+/// Whether `min_to_amount >= from_amount * price * (1 - max_slippage_bps / 10000)`,
+/// compared exactly by cross-multiplying
+pub fn meets_min_output(
+    from_amount: U256,
+    min_to_amount: U256,
+    price: U256,
+    max_slippage_bps: u32,
+) -> Result<bool, OracleError> {
+    let expected = scaled_min_output(from_amount, price, max_slippage_bps)?;
+    let offered = min_to_amount
+        .checked_mul(PRICE_SCALE)
+        .and_then(|v| v.checked_mul(U256::from(MAX_SLIPPAGE_BPS)))
+        .ok_or(OracleError::Overflow)?;
+    Ok(offered >= expected)
+}
+
+/// Smallest `min_to_amount` accepted by `meets_min_output`
+pub fn min_output(from_amount: U256, price: U256, max_slippage_bps: u32) -> Result<U256, OracleError> {
+    let expected = scaled_min_output(from_amount, price, max_slippage_bps)?;
+    Ok(expected.div_ceil(PRICE_SCALE * U256::from(MAX_SLIPPAGE_BPS)))
+}
@@ -0,0 +1,197 @@
+//! Compliance policies
+//!
+//! A policy is a set of predicates over a certificate's attributes, such as
+//! "jurisdiction not in [US]" or "age >= 18". The compliance circuit takes the
+//! policy as a public input, evaluates it against the private certificate and
+//! commits only the policy hash and the result, so a venue learns whether its
+//! policy holds without seeing the attributes.
+//!
+//! The policy hash is keccak256 of the ABI encoding of its predicates, whatever
+//! the active hash scheme, so venues can recompute it in Solidity. Jurisdiction
+//! lists are encoded sorted and deduplicated, so the same set always has the
+//! same hash.
+
+use crate::certificate::{AccreditationLevel, CertificateAttributes, CivilDate, Jurisdiction, KycTier};
+use crate::signature::keccak256;
+use alloy_sol_types::SolValue;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+mod encoding {
+    alloy_sol_types::sol! {
+        struct PolicyPredicate {
+            uint8 kind;
+            uint32 value;
+            bytes2[] jurisdictions;
+        }
+    }
+}
+
+/// Predicates per policy, bounding the circuit's cycle count
+pub const MAX_PREDICATES: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyError {
+    TooManyPredicates(usize),
+    /// A jurisdiction predicate with an empty list
+    EmptyJurisdictionList,
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyError::TooManyPredicates(n) => write!(f, "{} predicates, maximum is {}", n, MAX_PREDICATES),
+            PolicyError::EmptyJurisdictionList => write!(f, "jurisdiction predicate has an empty list"),
+        }
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+/// One condition on certificate attributes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Predicate {
+    JurisdictionIn(Vec<Jurisdiction>),
+    JurisdictionNotIn(Vec<Jurisdiction>),
+    MinKycTier(KycTier),
+    MinAccreditation(AccreditationLevel),
+    /// Completed years of age on the proof's timestamp (UTC)
+    MinAge(u32),
+}
+
+impl Predicate {
+    /// Whether `attributes` satisfy this predicate on `today`
+    pub fn holds(&self, attributes: &CertificateAttributes, today: CivilDate) -> bool {
+        match self {
+            Predicate::JurisdictionIn(list) => list.contains(&attributes.jurisdiction),
+            Predicate::JurisdictionNotIn(list) => !list.contains(&attributes.jurisdiction),
+            Predicate::MinKycTier(tier) => attributes.kyc_tier >= *tier,
+            Predicate::MinAccreditation(level) => attributes.accreditation >= *level,
+            Predicate::MinAge(years) => attributes.date_of_birth.years_until(today) >= *years,
+        }
+    }
+
+    fn to_sol(&self) -> encoding::PolicyPredicate {
+        let (kind, value, jurisdictions) = match self {
+            Predicate::JurisdictionIn(list) => (1, 0, list.as_slice()),
+            Predicate::JurisdictionNotIn(list) => (2, 0, list.as_slice()),
+            Predicate::MinKycTier(tier) => (3, *tier as u32, &[][..]),
+            Predicate::MinAccreditation(level) => (4, *level as u32, &[][..]),
+            Predicate::MinAge(years) => (5, *years, &[][..]),
+        };
+        let mut jurisdictions = jurisdictions.to_vec();
+        jurisdictions.sort();
+        jurisdictions.dedup();
+        encoding::PolicyPredicate {
+            kind,
+            value,
+            jurisdictions: jurisdictions.iter().map(|j| j.as_bytes().into()).collect(),
+        }
+    }
+}
+
+/// Predicates that must all hold
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Policy {
+    pub predicates: Vec<Predicate>,
+}
+
+impl Policy {
+    pub fn new(predicates: Vec<Predicate>) -> Result<Self, PolicyError> {
+        let policy = Self { predicates };
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    pub fn validate(&self) -> Result<(), PolicyError> {
+        if self.predicates.len() > MAX_PREDICATES {
+            return Err(PolicyError::TooManyPredicates(self.predicates.len()));
+        }
+        let empty_list = self.predicates.iter().any(|p| match p {
+            Predicate::JurisdictionIn(list) | Predicate::JurisdictionNotIn(list) => list.is_empty(),
+            _ => false,
+        });
+        if empty_list {
+            return Err(PolicyError::EmptyJurisdictionList);
+        }
+        Ok(())
+    }
+
+    /// keccak256(abi.encode(PolicyPredicate[])), with sorted jurisdiction lists
+    pub fn hash(&self) -> [u8; 32] {
+        let predicates: Vec<encoding::PolicyPredicate> = self.predicates.iter().map(Predicate::to_sol).collect();
+        keccak256(&predicates.abi_encode())
+    }
+
+    /// Whether every predicate holds for `attributes` at unix time `timestamp`
+    pub fn evaluate(&self, attributes: &CertificateAttributes, timestamp: u64) -> bool {
+        let today = CivilDate::from_timestamp(timestamp);
+        self.predicates.iter().all(|p| p.holds(attributes, today))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jurisdictions(codes: &[&str]) -> Vec<Jurisdiction> {
+        codes.iter().map(|c| Jurisdiction::new(c).unwrap()).collect()
+    }
+
+    fn born(date_of_birth: &str) -> CertificateAttributes {
+        CertificateAttributes {
+            jurisdiction: Jurisdiction::new("CH").unwrap(),
+            accreditation: AccreditationLevel::Accredited,
+            kyc_tier: KycTier::Standard,
+            date_of_birth: date_of_birth.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn policy_hash_matches_abi_encoding() {
+        let policy: Policy = serde_json::from_str(
+            r#"{"predicates": [{"jurisdictionNotIn": ["US", "KP"]}, {"minKycTier": "standard"}, {"minAge": 18}]}"#,
+        )
+        .unwrap();
+        // ethers keccak256(abi.encode(tuple(uint8,uint32,bytes2[])[])), jurisdictions sorted to [KP, US]
+        assert_eq!(
+            policy.hash(),
+            alloy_primitives::b256!("bc670f5311d3ede7b19a55c98cfe7dceeb047db0625075eed55c8a306b17fd16").0
+        );
+    }
+
+    #[test]
+    fn leap_day_birthdays_come_of_age_on_the_first_of_march() {
+        let adult = Policy::new(vec![Predicate::MinAge(18)]).unwrap();
+        assert!(!adult.evaluate(&born("2000-02-29"), 1_519_862_399)); // 2018-02-28T23:59:59Z
+        assert!(adult.evaluate(&born("2000-02-29"), 1_519_862_400)); // 2018-03-01T00:00:00Z
+        // Born after the proof's date
+        assert_eq!(born("2030-01-01").date_of_birth.years_until(CivilDate::from_timestamp(0)), 0);
+    }
+
+    #[test]
+    fn deserialized_policies_still_need_validating() {
+        let empty: Policy = serde_json::from_str(r#"{"predicates": [{"jurisdictionIn": []}]}"#).unwrap();
+        assert_eq!(empty.validate(), Err(PolicyError::EmptyJurisdictionList));
+
+        assert!(Policy::new(vec![Predicate::MinAge(18); MAX_PREDICATES]).is_ok());
+        assert_eq!(
+            Policy::new(vec![Predicate::MinAge(18); MAX_PREDICATES + 1]),
+            Err(PolicyError::TooManyPredicates(MAX_PREDICATES + 1))
+        );
+    }
+
+    #[test]
+    fn jurisdiction_order_and_duplicates_do_not_change_the_hash() {
+        let policy = |codes: &[&str]| Policy::new(vec![Predicate::JurisdictionNotIn(jurisdictions(codes))]).unwrap();
+        let canonical = policy(&["KP", "US"]).hash();
+        assert_eq!(policy(&["US", "KP"]).hash(), canonical);
+        assert_eq!(policy(&["US", "KP", "US", "KP"]).hash(), canonical);
+        assert_ne!(policy(&["KP"]).hash(), canonical);
+
+        // The same list under the other predicate kind is a different policy
+        let allow = Policy::new(vec![Predicate::JurisdictionIn(jurisdictions(&["KP", "US"]))]).unwrap();
+        assert_ne!(allow.hash(), canonical);
+    }
+}
@@ -0,0 +1,67 @@
+//! Certificate revocation list tree
+//!
+//! Sorted-leaf Merkle tree over the hashes of revoked compliance certificates
+//! (see `sorted_tree`). The compliance circuit takes the root as a public input
+//! and requires a non-membership witness for the certificate it checks, so a
+//! revoked certificate stops proving as soon as the authority publishes a new root.
+
+use crate::sorted_tree::{NonMembershipProof, SortedTree};
+
+/// Sorted tree of revoked certificate hashes, built on the host from the full list
+pub type RevocationTree = SortedTree<[u8; 32]>;
+
+/// Witness that a certificate hash is not revoked
+pub type RevocationProof = NonMembershipProof<[u8; 32]>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(fill: u8, last: u8) -> [u8; 32] {
+        let mut hash = [fill; 32];
+        hash[31] = last;
+        hash
+    }
+
+    #[test]
+    fn hashes_next_to_a_revoked_one_are_not_revoked() {
+        let revoked = hash(0x10, 0x10);
+        let tree = RevocationTree::new(&[revoked]);
+        assert!(tree.non_membership_proof(&revoked).is_none());
+
+        let above = hash(0x10, 0x11);
+        let proof = tree.non_membership_proof(&above).unwrap();
+        assert_eq!((proof.low, proof.high), (revoked, [0xff; 32]));
+        assert!(proof.verify(&above, &tree.root()));
+
+        let below = hash(0x10, 0x0f);
+        let proof = tree.non_membership_proof(&below).unwrap();
+        assert_eq!((proof.low, proof.high), ([0x00; 32], revoked));
+        assert!(proof.verify(&below, &tree.root()));
+        // The same witness does not cover the revoked hash it stops at
+        assert!(!proof.verify(&revoked, &tree.root()));
+    }
+
+    #[test]
+    fn witness_cannot_skip_over_a_revoked_hash() {
+        let tree = RevocationTree::new(&[[0x10; 32], [0x20; 32], [0x30; 32]]);
+        // Genuine paths of the leaves either side of the revoked [0x20; 32]
+        let forged = RevocationProof {
+            low: [0x10; 32],
+            high: [0x30; 32],
+            low_proof: tree.proof(1),
+            high_proof: tree.proof(3),
+        };
+        assert!(forged.low_proof.verify(&tree.root()).is_ok() && forged.high_proof.verify(&tree.root()).is_ok());
+        assert!(!forged.verify(&[0x20; 32], &tree.root()));
+    }
+
+    #[test]
+    fn empty_list_still_has_no_witness_for_the_sentinels() {
+        let tree = RevocationTree::new(&[]);
+        assert!(tree.is_empty());
+        assert!(tree.non_membership_proof(&[0x42; 32]).unwrap().verify(&[0x42; 32], &tree.root()));
+        assert!(tree.non_membership_proof(&[0x00; 32]).is_none());
+        assert!(tree.non_membership_proof(&[0xff; 32]).is_none());
+    }
+}
@@ -0,0 +1,23 @@
+//! Sanctions list tree
+//!
+//! Sorted-leaf Merkle tree over sanctioned addresses (see `sorted_tree`). Any
+//! address that is NOT in the list falls strictly between two adjacent leaves,
+//! which is what the innocence circuit checks.
+
+use crate::sorted_tree::{compute_key_leaf, SortedKey, SortedTree};
+
+pub use crate::sorted_tree::{NonMembershipProof, EMPTY_LEAF};
+
+/// Lower bound sentinel, always the first leaf of the tree
+pub const MIN_SENTINEL: [u8; 20] = <[u8; 20] as SortedKey>::MIN;
+
+/// Upper bound sentinel, always the last non-padding leaf of the tree
+pub const MAX_SENTINEL: [u8; 20] = <[u8; 20] as SortedKey>::MAX;
+
+/// Sorted sanctions tree, built on the host from the full list
+pub type SanctionsTree = SortedTree<[u8; 20]>;
+
+/// Hash an address into a tree leaf
+pub fn compute_address_leaf(address: &[u8; 20]) -> [u8; 32] {
+    compute_key_leaf(address)
+}
@@ -0,0 +1,58 @@
+//! Ethereum-style ECDSA helpers
+//!
+//! secp256k1 public key recovery over EIP-191 digests. Inside the zkVM the `k256`
+//! crate is patched to SP1's accelerated secp256k1 precompile (see the workspace
+//! `[patch.crates-io]`), so the same code path runs natively on the host.
+
+use crate::hash::{HashScheme, Keccak256Hash};
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+/// Signatures always use Keccak-256, whatever the active hash scheme
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256Hash::hash(data)
+}
+
+/// EIP-191 `personal_sign` digest of a 32-byte message hash:
+/// keccak256("\x19Ethereum Signed Message:\n32" || hash)
+pub fn eip191_digest(message_hash: &[u8; 32]) -> [u8; 32] {
+    let mut data = Vec::with_capacity(28 + 32);
+    data.extend_from_slice(b"\x19Ethereum Signed Message:\n32");
+    data.extend_from_slice(message_hash);
+    keccak256(&data)
+}
+
+/// Ethereum address of a secp256k1 public key
+pub fn public_key_to_address(key: &VerifyingKey) -> [u8; 20] {
+    let encoded = key.to_encoded_point(false);
+    // Skip the 0x04 uncompressed point prefix
+    let hash = keccak256(&encoded.as_bytes()[1..]);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+/// Parse a `0x`-prefixed, 40-digit hex Ethereum address
+pub(crate) fn parse_evm_address(s: &str) -> Option<[u8; 20]> {
+    let digits = s.trim().strip_prefix("0x")?;
+    if digits.len() != 40 {
+        return None;
+    }
+    let mut address = [0u8; 20];
+    for (i, byte) in address.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(digits.get(2 * i..2 * i + 2)?, 16).ok()?;
+    }
+    Some(address)
+}
+
+/// Recover the signer address from a prehashed digest and an (r, s, v) signature.
+/// `v` may be given as 0/1 or in the Ethereum 27/28 form.
+pub fn recover_signer(digest: &[u8; 32], v: u8, r: &[u8; 32], s: &[u8; 32]) -> Option<[u8; 20]> {
+    let recovery_id = RecoveryId::from_byte(if v >= 27 { v - 27 } else { v })?;
+    let signature = Signature::from_scalars(*r, *s).ok()?;
+    // Reject malleable high-s signatures, as Ethereum does
+    if signature.normalize_s().is_some() {
+        return None;
+    }
+    let key = VerifyingKey::recover_from_prehash(digest, &signature, recovery_id).ok()?;
+    Some(public_key_to_address(&key))
+}
@@ -0,0 +1,184 @@
+//! Sorted-leaf Merkle trees
+//!
+//! Merkle tree over a sorted list of keys, used to prove that a key is NOT in the
+//! list. Leaves are kept in strictly ascending order and bracketed by two sentinel
+//! keys (all zeros and all 0xff), so any key that is not listed falls strictly
+//! between two adjacent leaves.
+//!
+//! A non-membership witness is the pair of adjacent leaves (low, high) together with
+//! their Merkle paths. The verifier checks that both paths hash to the root, that the
+//! leaves sit at consecutive indices, and that low < key < high.
+//!
+//! A listed key is proven present by the Merkle path of its own leaf.
+//!
+//! The sanctions list and allowlist key addresses; the revocation list keys
+//! certificate hashes.
+
+use crate::{hash_pair, MerkleProof};
+use serde::{Deserialize, Serialize};
+
+/// Value used to pad the leaf layer up to a power of two
+pub const EMPTY_LEAF: [u8; 32] = [0u8; 32];
+
+/// Fixed-width key of a sorted tree
+pub trait SortedKey: Copy + Ord + AsRef<[u8]> {
+    /// Lower bound sentinel, always the first leaf of the tree
+    const MIN: Self;
+    /// Upper bound sentinel, always the last non-padding leaf of the tree
+    const MAX: Self;
+
+    /// Hash the key into its tree leaf
+    fn leaf(&self) -> [u8; 32] {
+        crate::hash::hash(self.as_ref())
+    }
+}
+
+impl SortedKey for [u8; 20] {
+    const MIN: Self = [0x00; 20];
+    const MAX: Self = [0xff; 20];
+}
+
+impl SortedKey for [u8; 32] {
+    const MIN: Self = [0x00; 32];
+    const MAX: Self = [0xff; 32];
+}
+
+/// Hash a key into a tree leaf
+pub fn compute_key_leaf<K: SortedKey>(key: &K) -> [u8; 32] {
+    key.leaf()
+}
+
+/// Sorted tree, built on the host from the full list
+#[derive(Debug, Clone)]
+pub struct SortedTree<K: SortedKey> {
+    keys: Vec<K>,
+    layers: Vec<Vec<[u8; 32]>>, // layers[0] = leaves, last = [root]
+}
+
+impl<K: SortedKey> SortedTree<K> {
+    /// Build the canonical tree from a list of keys in any order.
+    /// Duplicates are removed and the sentinels are added; a sentinel in `keys`
+    /// merges into the sentinel leaf and is not counted by `len`, so callers
+    /// loading external lists should reject sentinel keys first.
+    pub fn new(keys: &[K]) -> Self {
+        let mut sorted: Vec<K> = keys.to_vec();
+        sorted.push(K::MIN);
+        sorted.push(K::MAX);
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        let mut leaves: Vec<[u8; 32]> = sorted.iter().map(compute_key_leaf).collect();
+        let width = leaves.len().next_power_of_two();
+        leaves.resize(width, EMPTY_LEAF);
+
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| hash_pair(&pair[0], &pair[1]))
+                .collect();
+            layers.push(next);
+        }
+
+        Self { keys: sorted, layers }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.layers.last().unwrap()[0]
+    }
+
+    pub fn depth(&self) -> usize {
+        self.layers.len() - 1
+    }
+
+    /// Number of listed keys, excluding the sentinels
+    pub fn len(&self) -> usize {
+        self.keys.len() - 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.keys.binary_search(key).is_ok()
+    }
+
+    /// Merkle path for the leaf at `index`
+    pub fn proof(&self, index: usize) -> MerkleProof {
+        let mut path = Vec::with_capacity(self.depth());
+        let mut position = index;
+
+        for layer in &self.layers[..self.depth()] {
+            path.push(layer[position ^ 1]);
+            position >>= 1;
+        }
+
+        MerkleProof {
+            leaf: self.layers[0][index],
+            path,
+            leaf_index: index as u64,
+        }
+    }
+
+    /// Merkle path of a listed key's leaf.
+    /// Returns `None` if the key is not in the list (or is a sentinel).
+    pub fn membership_proof(&self, key: &K) -> Option<MerkleProof> {
+        if *key == K::MIN || *key == K::MAX {
+            return None;
+        }
+        let index = self.keys.binary_search(key).ok()?;
+        Some(self.proof(index))
+    }
+
+    /// Build a non-membership witness for `key`.
+    /// Returns `None` if the key is in the list (or is a sentinel).
+    pub fn non_membership_proof(&self, key: &K) -> Option<NonMembershipProof<K>> {
+        // Err(i) is the insertion point, so keys[i - 1] < key < keys[i]
+        let high_index = self.keys.binary_search(key).err()?;
+
+        Some(NonMembershipProof {
+            low: self.keys[high_index - 1],
+            high: self.keys[high_index],
+            low_proof: self.proof(high_index - 1),
+            high_proof: self.proof(high_index),
+        })
+    }
+}
+
+/// Witness that a key is absent from a sorted tree; addresses by default
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NonMembershipProof<K = [u8; 20]> {
+    pub low: K,
+    pub high: K,
+    pub low_proof: MerkleProof,
+    pub high_proof: MerkleProof,
+}
+
+impl<K: SortedKey> NonMembershipProof<K> {
+    pub fn verify(&self, key: &K, root: &[u8; 32]) -> bool {
+        // The key must sit strictly between the two neighbours
+        if !(self.low < *key && *key < self.high) {
+            return false;
+        }
+
+        // Both leaves must be the hashes of the claimed neighbours
+        if self.low_proof.leaf != compute_key_leaf(&self.low)
+            || self.high_proof.leaf != compute_key_leaf(&self.high)
+        {
+            return false;
+        }
+
+        // Both paths must come from the same tree depth and be adjacent
+        if self.low_proof.path.len() != self.high_proof.path.len() {
+            return false;
+        }
+        if self.low_proof.leaf_index.checked_add(1) != Some(self.high_proof.leaf_index) {
+            return false;
+        }
+
+        self.low_proof.verify(root).is_ok() && self.high_proof.verify(root).is_ok()
+    }
+}
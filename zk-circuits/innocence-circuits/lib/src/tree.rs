@@ -0,0 +1,243 @@
+//! Incremental commitment tree
+//!
+//! Fixed-depth, append-only Merkle tree in the style of Tornado Cash's
+//! `MerkleTreeWithHistory`. Empty positions hold precomputed zero-subtree hashes,
+//! so the root is defined for any number of leaves and an append only rehashes the
+//! path from the new leaf to the root. The last `ROOT_HISTORY_SIZE` roots are kept
+//! so a proof built against a slightly stale root is still recognised.
+//!
+//! The host keeps the full tree (every filled node) to extract a path for any leaf.
+//! Only the leaves and the root history are persisted; the nodes are rebuilt on load.
+
+use crate::{hash_pair, MerkleProof, MAX_MERKLE_DEPTH};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Value of an empty leaf
+pub const ZERO_VALUE: [u8; 32] = [0u8; 32];
+
+/// Deepest tree supported, the same bound proofs are verified against
+pub const MAX_TREE_DEPTH: usize = MAX_MERKLE_DEPTH;
+
+/// Depth of the deposit tree mirrored by the prover host
+pub const DEFAULT_TREE_DEPTH: usize = 20;
+
+/// Number of recent roots accepted by `is_known_root`
+pub const ROOT_HISTORY_SIZE: usize = 30;
+
+/// `zeros[i]` is the root of an empty subtree of height `i`, for `i` in `0..=depth`
+pub fn zero_hashes(depth: usize) -> Vec<[u8; 32]> {
+    let mut zeros = Vec::with_capacity(depth + 1);
+    zeros.push(ZERO_VALUE);
+    for i in 0..depth {
+        zeros.push(hash_pair(&zeros[i], &zeros[i]));
+    }
+    zeros
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeError {
+    /// Requested depth is zero or above `MAX_TREE_DEPTH`
+    InvalidDepth(usize),
+    /// Every leaf position is taken
+    TreeFull,
+    /// No leaf has been appended at this index
+    IndexOutOfRange(u64),
+    /// Persisted root history does not match the persisted leaves
+    RootMismatch,
+}
+
+impl fmt::Display for TreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TreeError::InvalidDepth(depth) => {
+                write!(f, "tree depth {} is outside 1..={}", depth, MAX_TREE_DEPTH)
+            }
+            TreeError::TreeFull => write!(f, "merkle tree is full"),
+            TreeError::IndexOutOfRange(index) => write!(f, "no leaf at index {}", index),
+            TreeError::RootMismatch => write!(f, "stored root does not match the stored leaves"),
+        }
+    }
+}
+
+impl std::error::Error for TreeError {}
+
+/// Append-only Merkle tree with root history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "TreeState", into = "TreeState")]
+pub struct IncrementalMerkleTree {
+    depth: usize,
+    zeros: Vec<[u8; 32]>,
+    layers: Vec<Vec<[u8; 32]>>, // layers[0] = leaves, only filled positions
+    roots: Vec<[u8; 32]>,       // ring buffer of ROOT_HISTORY_SIZE roots
+    current_root_index: usize,
+}
+
+impl IncrementalMerkleTree {
+    pub fn new(depth: usize) -> Result<Self, TreeError> {
+        if depth == 0 || depth > MAX_TREE_DEPTH {
+            return Err(TreeError::InvalidDepth(depth));
+        }
+
+        let zeros = zero_hashes(depth);
+        let mut roots = vec![[0u8; 32]; ROOT_HISTORY_SIZE];
+        roots[0] = zeros[depth];
+
+        Ok(Self {
+            depth,
+            zeros,
+            layers: vec![Vec::new(); depth],
+            roots,
+            current_root_index: 0,
+        })
+    }
+
+    /// Build a tree from leaves in insertion order
+    pub fn from_leaves(depth: usize, leaves: &[[u8; 32]]) -> Result<Self, TreeError> {
+        let mut tree = Self::new(depth)?;
+        for leaf in leaves {
+            tree.append(*leaf)?;
+        }
+        Ok(tree)
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Number of leaves appended so far
+    pub fn len(&self) -> u64 {
+        self.layers[0].len() as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers[0].is_empty()
+    }
+
+    pub fn capacity(&self) -> u64 {
+        1u64 << self.depth
+    }
+
+    pub fn leaves(&self) -> &[[u8; 32]] {
+        &self.layers[0]
+    }
+
+    pub fn zeros(&self) -> &[[u8; 32]] {
+        &self.zeros
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.roots[self.current_root_index]
+    }
+
+    /// Append a leaf and return its index
+    pub fn append(&mut self, leaf: [u8; 32]) -> Result<u64, TreeError> {
+        let index = self.len();
+        if index >= self.capacity() {
+            return Err(TreeError::TreeFull);
+        }
+
+        let mut position = index as usize;
+        let mut current = leaf;
+        for level in 0..self.depth {
+            // The rightmost node of each layer is either new or being updated
+            let layer = &mut self.layers[level];
+            if position == layer.len() {
+                layer.push(current);
+            } else {
+                layer[position] = current;
+            }
+
+            // An odd position's left sibling is filled; an even one pairs with a zero subtree
+            current = if position & 1 == 1 {
+                hash_pair(&layer[position - 1], &current)
+            } else {
+                hash_pair(&current, &self.zeros[level])
+            };
+            position >>= 1;
+        }
+
+        self.current_root_index = (self.current_root_index + 1) % ROOT_HISTORY_SIZE;
+        self.roots[self.current_root_index] = current;
+        Ok(index)
+    }
+
+    /// Whether `root` is the current root or one of the last `ROOT_HISTORY_SIZE` roots
+    pub fn is_known_root(&self, root: &[u8; 32]) -> bool {
+        // Unused history slots are zero; the zero root is never valid
+        *root != [0u8; 32] && self.roots.contains(root)
+    }
+
+    /// Roots from oldest to newest
+    pub fn root_history(&self) -> Vec<[u8; 32]> {
+        (1..=ROOT_HISTORY_SIZE)
+            .map(|offset| self.roots[(self.current_root_index + offset) % ROOT_HISTORY_SIZE])
+            .filter(|root| *root != [0u8; 32])
+            .collect()
+    }
+
+    /// Index of the first occurrence of `leaf`
+    pub fn position(&self, leaf: &[u8; 32]) -> Option<u64> {
+        self.layers[0].iter().position(|l| l == leaf).map(|i| i as u64)
+    }
+
+    /// Merkle path for the leaf at `index` against the current root
+    pub fn proof(&self, index: u64) -> Result<MerkleProof, TreeError> {
+        if index >= self.len() {
+            return Err(TreeError::IndexOutOfRange(index));
+        }
+
+        let mut path = Vec::with_capacity(self.depth);
+        let mut position = index as usize;
+
+        for level in 0..self.depth {
+            let sibling = position ^ 1;
+            path.push(self.layers[level].get(sibling).copied().unwrap_or(self.zeros[level]));
+            position >>= 1;
+        }
+
+        Ok(MerkleProof {
+            leaf: self.layers[0][index as usize],
+            path,
+            leaf_index: index,
+        })
+    }
+}
+
+/// Persisted form of `IncrementalMerkleTree`
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TreeState {
+    depth: usize,
+    leaves: Vec<[u8; 32]>,
+    roots: Vec<[u8; 32]>,
+    current_root_index: usize,
+}
+
+impl From<IncrementalMerkleTree> for TreeState {
+    fn from(mut tree: IncrementalMerkleTree) -> Self {
+        Self {
+            depth: tree.depth,
+            leaves: std::mem::take(&mut tree.layers[0]),
+            roots: tree.roots,
+            current_root_index: tree.current_root_index,
+        }
+    }
+}
+
+impl TryFrom<TreeState> for IncrementalMerkleTree {
+    type Error = TreeError;
+
+    fn try_from(state: TreeState) -> Result<Self, Self::Error> {
+        let mut tree = Self::from_leaves(state.depth, &state.leaves)?;
+        if state.roots.len() != ROOT_HISTORY_SIZE
+            || state.current_root_index >= ROOT_HISTORY_SIZE
+            || state.roots[state.current_root_index] != tree.root()
+        {
+            return Err(TreeError::RootMismatch);
+        }
+        tree.roots = state.roots;
+        tree.current_root_index = state.current_root_index;
+        Ok(tree)
+    }
+}
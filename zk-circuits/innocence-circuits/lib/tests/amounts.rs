@@ -0,0 +1,46 @@
+//! Decimal amount strings to and from base units.
+
+use innocence_circuits_lib::{format_units, parse_units, AmountError, U256};
+
+#[test]
+fn parses_decimal_strings() {
+    assert_eq!(parse_units("1000", 0), Ok(U256::from(1000u64)));
+    assert_eq!(parse_units("1.5", 6), Ok(U256::from(1_500_000u64)));
+    assert_eq!(parse_units(".25", 2), Ok(U256::from(25u64)));
+    assert_eq!(parse_units("7.", 1), Ok(U256::from(70u64)));
+    assert_eq!(parse_units("1.50", 1), Ok(U256::from(15u64)));
+    assert_eq!(
+        parse_units("1234.5", 18),
+        Ok(U256::from(1_234_500_000_000_000_000_000u128))
+    );
+}
+
+#[test]
+fn rejects_malformed_amounts() {
+    assert_eq!(parse_units("", 18), Err(AmountError::Empty));
+    assert_eq!(parse_units(".", 18), Err(AmountError::Empty));
+    assert_eq!(parse_units("-1", 18), Err(AmountError::InvalidCharacter('-')));
+    assert_eq!(parse_units("1.2.3", 18), Err(AmountError::InvalidCharacter('.')));
+    assert_eq!(parse_units("1e18", 18), Err(AmountError::InvalidCharacter('e')));
+    assert_eq!(parse_units("0.001", 2), Err(AmountError::TooManyDecimals { decimals: 2 }));
+    assert_eq!(parse_units("1", 78), Err(AmountError::InvalidDecimals(78)));
+}
+
+#[test]
+fn bounded_by_u256() {
+    let max = U256::MAX.to_string();
+    assert_eq!(parse_units(&max, 0), Ok(U256::MAX));
+    assert_eq!(parse_units(&format!("{}0", max), 0), Err(AmountError::Overflow));
+    assert_eq!(parse_units(&max, 1), Err(AmountError::Overflow));
+}
+
+#[test]
+fn formats_base_units() {
+    assert_eq!(format_units(U256::from(1_500_000u64), 6), "1.5");
+    assert_eq!(format_units(U256::from(25u64), 2), "0.25");
+    assert_eq!(format_units(U256::from(1000u64), 0), "1000");
+    assert_eq!(format_units(U256::from(3_000_000u64), 6), "3");
+
+    let amount = parse_units("1234.000000000000000001", 18).unwrap();
+    assert_eq!(format_units(amount, 18), "1234.000000000000000001");
+}
@@ -0,0 +1,95 @@
+//! Asset registry loading and lookups.
+
+use innocence_circuits_lib::{Asset, AssetId, AssetRegistry, RegistryError, U256};
+use std::collections::BTreeMap;
+
+fn asset(id: u64, symbol: &str, decimals: u8) -> Asset {
+    Asset {
+        id: AssetId(id),
+        symbol: symbol.to_string(),
+        decimals,
+        native: false,
+        hypercore_index: None,
+        evm: BTreeMap::new(),
+    }
+}
+
+#[test]
+fn builtin_registry_matches_dex_token_ids() {
+    let registry = AssetRegistry::builtin();
+
+    // PrivateDEXExtension.tokenAddresses on HyperEVM mainnet
+    let dex = [
+        (0, "0x5555555555555555555555555555555555555555"),
+        (1, "0x9FDBdA0A5e284c32744D2f17Ee5c74B284993463"),
+        (2, "0xBe6727B535545C67d5cAa73dEa54865B92CF7907"),
+        (3, "0x5d3a1Ff2b6BAb83b63cd9AD0787074081a52ef34"),
+    ];
+    for (id, address) in dex {
+        let asset = registry.check(AssetId(id)).unwrap();
+        assert_eq!(asset.evm["hyperevm"], address);
+        assert!(asset.evm_address("hyperevm").is_some());
+    }
+    assert!(registry.get(AssetId(0)).unwrap().native);
+}
+
+#[test]
+fn resolves_symbols_and_ids() {
+    let registry = AssetRegistry::builtin();
+    let usdc = registry.resolve("USDC").unwrap();
+    assert_eq!(registry.resolve("usdc").unwrap(), usdc);
+    assert_eq!(registry.resolve(&usdc.id.to_string()).unwrap(), usdc);
+    assert_eq!(usdc.parse_amount("1.5"), Ok(U256::from(1_500_000u64)));
+    assert_eq!(usdc.format_amount(U256::from(2_500_000u64)), "2.5");
+
+    assert_eq!(registry.resolve("DOGE"), Err(RegistryError::UnknownAsset("DOGE".to_string())));
+    assert_eq!(registry.resolve("99"), Err(RegistryError::UnknownAssetId(AssetId(99))));
+}
+
+#[test]
+fn json_and_toml_agree() {
+    let toml = r#"
+        [[assets]]
+        id = 7
+        symbol = "TST"
+        decimals = 6
+        hypercore_index = 12
+
+        [assets.evm]
+        hyperevm-testnet = "0xfC2348222447c85779Eebb46782335cdB5B56303"
+    "#;
+    let json = r#"{"assets": [{"id": 7, "symbol": "TST", "decimals": 6, "hypercore_index": 12,
+        "evm": {"hyperevm-testnet": "0xfC2348222447c85779Eebb46782335cdB5B56303"}}]}"#;
+
+    let from_toml = AssetRegistry::from_toml(toml).unwrap();
+    assert_eq!(AssetRegistry::from_json(json).unwrap(), from_toml);
+
+    let tst = from_toml.check(AssetId(7)).unwrap();
+    assert_eq!(tst.evm_address("hyperevm-testnet").unwrap()[0], 0xfc);
+    assert_eq!(tst.evm_address("hyperevm"), None);
+}
+
+#[test]
+fn rejects_inconsistent_registries() {
+    assert_eq!(
+        AssetRegistry::new(vec![asset(1, "A", 6), asset(1, "B", 6)]),
+        Err(RegistryError::DuplicateId(AssetId(1)))
+    );
+    assert_eq!(
+        AssetRegistry::new(vec![asset(1, "usdc", 6), asset(2, "USDC", 6)]),
+        Err(RegistryError::DuplicateSymbol("USDC".to_string()))
+    );
+    assert!(matches!(
+        AssetRegistry::new(vec![asset(1, "A", 78)]),
+        Err(RegistryError::InvalidDecimals { .. })
+    ));
+
+    let mut bad = asset(1, "A", 6);
+    bad.evm.insert("hyperevm".to_string(), "0x1234".to_string());
+    assert!(matches!(AssetRegistry::new(vec![bad]), Err(RegistryError::InvalidAddress { .. })));
+
+    // Deserialising runs the same checks
+    assert!(AssetRegistry::from_json(r#"{"assets": [{"id": 1, "symbol": "A", "decimals": 6},
+        {"id": 1, "symbol": "B", "decimals": 6}]}"#)
+    .is_err());
+}
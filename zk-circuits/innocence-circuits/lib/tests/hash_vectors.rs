@@ -0,0 +1,112 @@
+//! Golden vectors for the hash schemes.
+//!
+//! Keccak vectors were produced with ethers v6 from the Solidity encodings used by
+//! the contracts and the proof service:
+//!   commitment    = keccak256(abi.encode(bytes32 secret, bytes32 nullifier))
+//!   nullifierHash = keccak256(bytes32 nullifier)
+//!   amount commit = keccak256(abi.encodePacked(uint256 amount, bytes32 amountBlinding))
+//!   leaf          = keccak256(abi.encodePacked(bytes32 commitment, uint64 assetId, bytes32 amountCommitment))
+//!   address leaf  = keccak256(abi.encodePacked(address))
+
+use innocence_circuits_lib::hash::{HashScheme, Keccak256Hash, Sha256Hash};
+use innocence_circuits_lib::U256;
+
+const SECRET: [u8; 32] = [0x01; 32];
+const NULLIFIER: [u8; 32] = [0x02; 32];
+const AMOUNT_BLINDING: [u8; 32] = [0x03; 32];
+
+fn h(s: &str) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[2 + 2 * i..4 + 2 * i], 16).unwrap();
+    }
+    out
+}
+
+const KECCAK_COMMITMENT: &str = "0x346d8c96a2454213fcc0daff3c96ad0398148181b9fa6488f7ae2c0af5b20aa0";
+const KECCAK_NULLIFIER_HASH: &str = "0xee4a079f5b14a24465181d45af32a8053c2d446446d7019359e210b82e53b8ba";
+const KECCAK_LEAF: &str = "0xc4c0bca76945c5308df1b1b7998f9a152a1d3e6a4597622cbe5ec08202e6d163";
+const KECCAK_ADDRESS_LEAF: &str = "0x932e768c7cdb7b2c0881cc3eaefda5a6b6021c72d3f2db766eaf440f05c0977d";
+
+#[test]
+fn keccak_matches_solidity_encodings() {
+    let commitment = Keccak256Hash::hash_pair(&SECRET, &NULLIFIER);
+    assert_eq!(commitment, h(KECCAK_COMMITMENT));
+    assert_eq!(Keccak256Hash::hash(&NULLIFIER), h(KECCAK_NULLIFIER_HASH));
+
+    let mut amount = U256::from(1000u64).to_be_bytes::<32>().to_vec();
+    amount.extend_from_slice(&AMOUNT_BLINDING);
+    let mut packed = commitment.to_vec();
+    packed.extend_from_slice(&0u64.to_be_bytes());
+    packed.extend_from_slice(&Keccak256Hash::hash(&amount));
+    assert_eq!(Keccak256Hash::hash(&packed), h(KECCAK_LEAF));
+
+    let address: [u8; 20] = h("0x8589427373d6d84e98730d7795d8f6f8731fda16000000000000000000000000")[..20]
+        .try_into()
+        .unwrap();
+    assert_eq!(Keccak256Hash::hash(&address), h(KECCAK_ADDRESS_LEAF));
+}
+
+#[test]
+fn sha256_vectors() {
+    assert_eq!(
+        Sha256Hash::hash_pair(&SECRET, &NULLIFIER),
+        h("0xf818afd37a6dc3bc92fb44731011277006db4efa6e9023cd7468c02335d22a4d")
+    );
+    assert_eq!(
+        Sha256Hash::hash(&NULLIFIER),
+        h("0x75877bb41d393b5fb8455ce60ecd8dda001d06316496b14dfa7f895656eeca4a")
+    );
+}
+
+#[cfg(not(any(feature = "sha256", feature = "poseidon2")))]
+#[test]
+fn active_scheme_matches_contracts() {
+    use innocence_circuits_lib::{
+        compute_commitment, compute_note_leaf, compute_nullifier_hash, sanctions,
+    };
+
+    let commitment = compute_commitment(&SECRET, &NULLIFIER);
+    assert_eq!(commitment, h(KECCAK_COMMITMENT));
+    assert_eq!(compute_nullifier_hash(&NULLIFIER), h(KECCAK_NULLIFIER_HASH));
+    assert_eq!(compute_note_leaf(&commitment, 0, U256::from(1000u64), &AMOUNT_BLINDING), h(KECCAK_LEAF));
+
+    let address: [u8; 20] = h("0x8589427373d6d84e98730d7795d8f6f8731fda16000000000000000000000000")[..20]
+        .try_into()
+        .unwrap();
+    assert_eq!(sanctions::compute_address_leaf(&address), h(KECCAK_ADDRESS_LEAF));
+}
+
+#[cfg(feature = "poseidon2")]
+#[test]
+fn poseidon2_is_length_separated() {
+    use innocence_circuits_lib::hash::Poseidon2Hash;
+
+    assert_ne!(Poseidon2Hash::hash(&[]), Poseidon2Hash::hash(&[0]));
+    assert_ne!(Poseidon2Hash::hash(&[0, 0, 0]), Poseidon2Hash::hash(&[0, 0, 0, 0]));
+}
+
+/// A note's commitment is the deposit commitment and its leaf the deposit tree leaf
+#[cfg(not(any(feature = "sha256", feature = "poseidon2")))]
+#[test]
+fn keccak_note_leaf() {
+    let mut note = innocence_circuits_lib::Note {
+        asset_id: 7,
+        amount: U256::from(1000u64),
+        secret: SECRET,
+        blinding: NULLIFIER,
+        amount_blinding: AMOUNT_BLINDING,
+    };
+    assert_eq!(note.commitment(), h(KECCAK_COMMITMENT));
+    assert_eq!(
+        note.leaf(),
+        h("0x6a49de9cb84d4b5806df0e83c42d234ea1a7fe29f8887c0bba6f833f5c40b7b5")
+    );
+
+    // 1234.5 tokens with 18 decimals, well above u64::MAX base units
+    note.amount = innocence_circuits_lib::parse_units("1234.5", 18).unwrap();
+    assert_eq!(
+        note.leaf(),
+        h("0x94a999cca91511e6a7616d53fef341e41341a18b9120bfe09e6bfe0724844716")
+    );
+}
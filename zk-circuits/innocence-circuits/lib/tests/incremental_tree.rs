@@ -0,0 +1,84 @@
+//! Incremental tree against a naively built full tree.
+
+use innocence_circuits_lib::tree::{zero_hashes, ROOT_HISTORY_SIZE, ZERO_VALUE};
+use innocence_circuits_lib::{hash_pair, IncrementalMerkleTree, TreeError};
+
+fn leaf(i: u64) -> [u8; 32] {
+    let mut out = [0xaa; 32];
+    out[24..].copy_from_slice(&i.to_be_bytes());
+    out
+}
+
+/// Root of a depth-`depth` tree with `leaves` on the left and zeros elsewhere
+fn naive_root(depth: usize, leaves: &[[u8; 32]]) -> [u8; 32] {
+    let mut layer = leaves.to_vec();
+    layer.resize(1 << depth, ZERO_VALUE);
+    while layer.len() > 1 {
+        layer = layer.chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+    }
+    layer[0]
+}
+
+#[test]
+fn roots_and_proofs_match_full_tree() {
+    let depth = 4;
+    let mut tree = IncrementalMerkleTree::new(depth).unwrap();
+    assert_eq!(tree.root(), zero_hashes(depth)[depth]);
+
+    let mut leaves = Vec::new();
+    for i in 0..16 {
+        leaves.push(leaf(i));
+        assert_eq!(tree.append(leaf(i)).unwrap(), i);
+        assert_eq!(tree.root(), naive_root(depth, &leaves));
+
+        for j in 0..=i {
+            let proof = tree.proof(j).unwrap();
+            assert_eq!(proof.leaf, leaf(j));
+            assert_eq!(proof.leaf_index, j);
+            assert_eq!(proof.verify(&tree.root()), Ok(()));
+        }
+    }
+
+    assert_eq!(tree.append(leaf(16)), Err(TreeError::TreeFull));
+    assert_eq!(tree.proof(16).unwrap_err(), TreeError::IndexOutOfRange(16));
+}
+
+#[test]
+fn root_history_keeps_recent_roots() {
+    let mut tree = IncrementalMerkleTree::new(8).unwrap();
+    let first_root = tree.root();
+    let mut roots = vec![first_root];
+    for i in 0..ROOT_HISTORY_SIZE as u64 {
+        tree.append(leaf(i)).unwrap();
+        roots.push(tree.root());
+    }
+
+    // The empty root has been overwritten, every later one is still known
+    assert!(!tree.is_known_root(&first_root));
+    assert!(roots[1..].iter().all(|root| tree.is_known_root(root)));
+    assert_eq!(tree.root_history(), roots[1..]);
+    assert!(!tree.is_known_root(&[0u8; 32]));
+}
+
+#[test]
+fn serde_round_trip() {
+    let leaves: Vec<[u8; 32]> = (0..5).map(leaf).collect();
+    let tree = IncrementalMerkleTree::from_leaves(20, &leaves).unwrap();
+
+    let json = serde_json::to_string(&tree).unwrap();
+    let restored: IncrementalMerkleTree = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.root(), tree.root());
+    assert_eq!(restored.root_history(), tree.root_history());
+    assert_eq!(restored.leaves(), tree.leaves());
+
+    // A tampered leaf no longer matches the stored root
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    value["leaves"][0][0] = 0.into();
+    assert!(serde_json::from_value::<IncrementalMerkleTree>(value).is_err());
+}
+
+#[test]
+fn rejects_invalid_depth() {
+    assert_eq!(IncrementalMerkleTree::new(0).unwrap_err(), TreeError::InvalidDepth(0));
+    assert_eq!(IncrementalMerkleTree::new(33).unwrap_err(), TreeError::InvalidDepth(33));
+}
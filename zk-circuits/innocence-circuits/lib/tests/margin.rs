@@ -0,0 +1,81 @@
+//! Margin account health at mark prices.
+
+use innocence_circuits_lib::margin::MAX_POSITIONS;
+use innocence_circuits_lib::oracle::PRICE_SCALE;
+use innocence_circuits_lib::{MarginAccount, MarginError, MarkPrice, Position, Side, U256};
+
+fn price(units: u64) -> U256 {
+    PRICE_SCALE * U256::from(units)
+}
+
+fn account(collateral: u64, side: Side) -> MarginAccount {
+    MarginAccount {
+        owner: [1u8; 32],
+        collateral_asset: 4,
+        collateral: U256::from(collateral),
+        positions: vec![Position { asset_id: 2, side, size: U256::from(10u64), entry_price: price(10) }],
+        blinding: [3u8; 32],
+    }
+}
+
+fn mark(units: u64, bps: u32) -> Vec<MarkPrice> {
+    vec![MarkPrice { assetId: 2, price: price(units), maintenanceMarginBps: bps }]
+}
+
+#[test]
+fn long_losses_count_against_collateral() {
+    // 10 units bought at 10, marked at 9: 10 lost, 90 notional at 10% needs 9
+    let healthy = account(19, Side::Long).health(&mark(9, 1000)).unwrap();
+    assert!(healthy.is_healthy());
+    assert_eq!(healthy.equity(), Some(U256::from(9u64)));
+    assert_eq!(healthy.maintenance_margin(), U256::from(9u64));
+
+    assert!(!account(18, Side::Long).health(&mark(9, 1000)).unwrap().is_healthy());
+
+    let underwater = account(5, Side::Long).health(&mark(9, 1000)).unwrap();
+    assert!(!underwater.is_healthy());
+    assert_eq!(underwater.equity(), None);
+}
+
+#[test]
+fn short_gains_when_the_price_falls() {
+    let health = account(0, Side::Short).health(&mark(9, 1000)).unwrap();
+    assert!(health.is_healthy());
+    assert_eq!(health.equity(), Some(U256::from(10u64)));
+
+    assert!(!account(0, Side::Short).health(&mark(11, 1000)).unwrap().is_healthy());
+}
+
+#[test]
+fn mark_prices_are_validated() {
+    let long = account(100, Side::Long);
+    assert_eq!(long.health(&[]), Err(MarginError::MissingMarkPrice(2)));
+
+    let mut duplicate = mark(9, 500);
+    duplicate.extend(mark(10, 500));
+    assert_eq!(long.health(&duplicate), Err(MarginError::DuplicateMarkPrice(2)));
+
+    assert_eq!(
+        long.health(&mark(9, 10_001)),
+        Err(MarginError::InvalidMarginBps { asset_id: 2, bps: 10_001 })
+    );
+
+    let mut crowded = long.clone();
+    crowded.positions = vec![long.positions[0].clone(); MAX_POSITIONS + 1];
+    assert_eq!(crowded.health(&mark(9, 500)), Err(MarginError::TooManyPositions(MAX_POSITIONS + 1)));
+}
+
+#[test]
+fn commitment_binds_every_position_field() {
+    let long = account(100, Side::Long);
+    assert_ne!(long.commitment(), account(100, Side::Short).commitment());
+    assert_ne!(long.commitment(), account(101, Side::Long).commitment());
+
+    let mut moved = long.clone();
+    moved.positions[0].entry_price = price(11);
+    assert_ne!(long.commitment(), moved.commitment());
+
+    let mut reblinded = long.clone();
+    reblinded.blinding = [4u8; 32];
+    assert_ne!(long.commitment(), reblinded.commitment());
+}
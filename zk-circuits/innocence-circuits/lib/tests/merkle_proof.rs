@@ -0,0 +1,51 @@
+//! Malformed Merkle witnesses fail with an error instead of panicking.
+
+use innocence_circuits_lib::{IncrementalMerkleTree, MerkleError, MAX_MERKLE_DEPTH};
+
+fn sample_tree() -> IncrementalMerkleTree {
+    let leaves: Vec<[u8; 32]> = (1..=5u8).map(|i| [i; 32]).collect();
+    IncrementalMerkleTree::from_leaves(4, &leaves).unwrap()
+}
+
+#[test]
+fn index_is_packed_into_u64() {
+    let tree = sample_tree();
+    let proof = tree.proof(5 - 1).unwrap();
+    assert_eq!(proof.leaf_index, 0b0100);
+    assert_eq!(proof.depth(), 4);
+    assert_eq!(proof.compute_root(), Ok(tree.root()));
+}
+
+#[test]
+fn rejects_index_beyond_depth() {
+    let tree = sample_tree();
+    let mut proof = tree.proof(2).unwrap();
+
+    // Same low bits, extra high bit: must not alias leaf 2
+    proof.leaf_index |= 1 << 4;
+    assert_eq!(
+        proof.verify(&tree.root()),
+        Err(MerkleError::IndexOutOfRange { index: 18, depth: 4 })
+    );
+}
+
+#[test]
+fn rejects_wrong_position_and_root() {
+    let tree = sample_tree();
+    let mut proof = tree.proof(2).unwrap();
+    proof.leaf_index = 3;
+    assert_eq!(proof.verify(&tree.root()), Err(MerkleError::RootMismatch));
+
+    let proof = tree.proof(2).unwrap();
+    assert_eq!(proof.verify(&[0u8; 32]), Err(MerkleError::RootMismatch));
+}
+
+#[test]
+fn rejects_paths_deeper_than_the_bound() {
+    let mut proof = sample_tree().proof(0).unwrap();
+    proof.path = vec![[0u8; 32]; MAX_MERKLE_DEPTH + 1];
+    assert_eq!(
+        proof.compute_root(),
+        Err(MerkleError::DepthTooLarge { depth: MAX_MERKLE_DEPTH + 1 })
+    );
+}
@@ -0,0 +1,57 @@
+//! Note ownership through public note keys and key-derived nullifiers.
+
+use innocence_circuits_lib::{note_public_key, Note, U256};
+
+fn note_for(owner: [u8; 32]) -> Note {
+    Note {
+        asset_id: 7,
+        amount: U256::from(1000u64),
+        secret: owner,
+        blinding: [2u8; 32],
+        amount_blinding: [3u8; 32],
+    }
+}
+
+#[test]
+fn only_the_spending_key_owns_its_notes() {
+    let spending_key = [9u8; 32];
+    let note = note_for(note_public_key(&spending_key));
+
+    assert!(note.is_owned_by(&spending_key));
+    assert!(!note.is_owned_by(&[8u8; 32]));
+    // Knowing the public key (the note's secret) is not enough to spend
+    assert!(!note.is_owned_by(&note.secret));
+}
+
+#[test]
+fn public_key_is_domain_separated() {
+    let key = [9u8; 32];
+    assert_ne!(note_public_key(&key), innocence_circuits_lib::hash::hash(&key));
+    assert_ne!(note_public_key(&key), note_public_key(&[0u8; 32]));
+}
+
+#[test]
+fn nullifier_hash_needs_the_spending_key() {
+    let spending_key = [9u8; 32];
+    let note = note_for(note_public_key(&spending_key));
+    let nullifier_hash = note.nullifier_hash(&spending_key, 5);
+
+    // Every note field is known to the sender, none of them gives the nullifier hash
+    assert_ne!(note.nullifier_hash(&note.secret, 5), nullifier_hash);
+    assert_ne!(innocence_circuits_lib::compute_nullifier_hash(&note.blinding), nullifier_hash);
+    assert_ne!(note.nullifier_hash(&[8u8; 32], 5), nullifier_hash);
+}
+
+#[test]
+fn nullifier_hash_is_bound_to_the_position() {
+    let spending_key = [9u8; 32];
+    let note = note_for(note_public_key(&spending_key));
+
+    // A copy of the note at another leaf has its own nullifier hash
+    assert_eq!(note.nullifier_hash(&spending_key, 5), note.nullifier_hash(&spending_key, 5));
+    assert_ne!(note.nullifier_hash(&spending_key, 5), note.nullifier_hash(&spending_key, 6));
+
+    let mut other = note.clone();
+    other.blinding = [3u8; 32];
+    assert_ne!(other.nullifier_hash(&spending_key, 5), note.nullifier_hash(&spending_key, 5));
+}
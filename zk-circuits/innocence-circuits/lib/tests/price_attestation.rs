@@ -0,0 +1,64 @@
+//! Oracle price attestations and the slippage bound on swap outputs.
+
+use innocence_circuits_lib::oracle::{attestation_digest, meets_min_output, min_output, PRICE_SCALE};
+use innocence_circuits_lib::signature::public_key_to_address;
+use innocence_circuits_lib::{OracleError, PriceAttestation, SignedPrice, U256};
+use k256::ecdsa::SigningKey;
+
+fn sign(key: &SigningKey, price: U256) -> SignedPrice {
+    let attestation = PriceAttestation { baseAsset: 3, quoteAsset: 2, price, timestamp: 1_700_000_000 };
+    let (signature, recovery_id) = key.sign_prehash_recoverable(&attestation_digest(&attestation)).unwrap();
+    let (r, s) = signature.split_bytes();
+    SignedPrice {
+        base_asset: 3,
+        quote_asset: 2,
+        price,
+        timestamp: 1_700_000_000,
+        signer: public_key_to_address(key.verifying_key()),
+        v: 27 + recovery_id.to_byte(),
+        r: r.into(),
+        s: s.into(),
+    }
+}
+
+#[test]
+fn attestation_verifies_only_for_its_oracle() {
+    let key = SigningKey::from_slice(&[7u8; 32]).unwrap();
+    let oracle = public_key_to_address(key.verifying_key());
+    let signed = sign(&key, PRICE_SCALE / U256::from(3000u64));
+
+    assert_eq!(signed.verify(&oracle), Ok(()));
+    assert_eq!(
+        signed.verify(&[0x11; 20]),
+        Err(OracleError::UntrustedSigner { signer: oracle })
+    );
+
+    let mut tampered = signed.clone();
+    tampered.price += U256::from(1u64);
+    assert_eq!(tampered.verify(&oracle), Err(OracleError::InvalidSignature));
+}
+
+#[test]
+fn min_output_is_the_exact_slippage_bound() {
+    // 1000 base units at 2.5 quote units each, 50 bps slippage: 2487.5 rounds up to 2488
+    let price = PRICE_SCALE * U256::from(5u64) / U256::from(2u64);
+    let from = U256::from(1000u64);
+    assert_eq!(min_output(from, price, 50), Ok(U256::from(2488u64)));
+    assert_eq!(meets_min_output(from, U256::from(2488u64), price, 50), Ok(true));
+    assert_eq!(meets_min_output(from, U256::from(2487u64), price, 50), Ok(false));
+
+    // Without slippage the full attested output is required
+    assert_eq!(meets_min_output(from, U256::from(2500u64), price, 0), Ok(true));
+    assert_eq!(meets_min_output(from, U256::from(2499u64), price, 0), Ok(false));
+}
+
+#[test]
+fn slippage_is_capped_and_overflow_is_reported() {
+    let from = U256::from(1000u64);
+    assert_eq!(min_output(from, PRICE_SCALE, 10_000), Ok(U256::ZERO));
+    assert_eq!(
+        meets_min_output(from, U256::ZERO, PRICE_SCALE, 10_001),
+        Err(OracleError::SlippageTooLarge(10_001))
+    );
+    assert_eq!(min_output(U256::MAX, PRICE_SCALE, 0), Err(OracleError::Overflow));
+}
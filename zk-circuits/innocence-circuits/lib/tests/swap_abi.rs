@@ -0,0 +1,105 @@
+//! `SwapProofPublicValues` against the struct `PrivateDEXExtension.privateSwap` decodes.
+//!
+//! The expected encoding was produced with ethers v6:
+//!   AbiCoder.encode(["tuple(bytes32,bytes32,uint64,uint64,uint256,uint256,bytes32)"], [values])
+
+use alloy_sol_types::private::U256;
+use alloy_sol_types::{SolStruct, SolType};
+use innocence_circuits_lib::{SwapNoteProofPublicValues, SwapProofPublicValues};
+
+const CONTRACT: &str = "../../../contracts/contracts/PrivateDEXExtension.sol";
+
+const ENCODED: &str = "\
+1111111111111111111111111111111111111111111111111111111111111111\
+2222222222222222222222222222222222222222222222222222222222222222\
+0000000000000000000000000000000000000000000000000000000000000003\
+0000000000000000000000000000000000000000000000000000000000000007\
+00000000000000000000000000000000000000000000003635c9adc5dea00000\
+0000000000000100000000000000000000000000000000000000000000000005\
+3333333333333333333333333333333333333333333333333333333333333333";
+
+/// `Name(type field,...)` for a struct in a Solidity source file
+fn solidity_struct(source: &str, name: &str) -> String {
+    let start = source
+        .find(&format!("struct {} {{", name))
+        .unwrap_or_else(|| panic!("struct {} not found", name));
+    let body = &source[start..];
+    let body = &body[body.find('{').unwrap() + 1..body.find('}').unwrap()];
+
+    let fields: Vec<String> = body
+        .split(';')
+        .map(|field| {
+            let field = field.lines().filter(|l| !l.trim_start().starts_with("//")).collect::<Vec<_>>().join(" ");
+            field.split_whitespace().collect::<Vec<_>>().join(" ")
+        })
+        .filter(|field| !field.is_empty())
+        .collect();
+    format!("{}({})", name, fields.join(","))
+}
+
+fn sample() -> SwapProofPublicValues {
+    SwapProofPublicValues {
+        commitment: [0x11; 32].into(),
+        nullifierHash: [0x22; 32].into(),
+        tokenIn: 3,
+        tokenOut: 7,
+        amountIn: U256::from(1_000u64) * U256::from(10u64).pow(U256::from(18u64)),
+        minAmountOut: (U256::from(1u64) << 200) + U256::from(5u64),
+        merkleRoot: [0x33; 32].into(),
+    }
+}
+
+#[test]
+fn layout_matches_solidity_struct() {
+    let path = format!("{}/{}", env!("CARGO_MANIFEST_DIR"), CONTRACT);
+    let source = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+
+    assert_eq!(
+        SwapProofPublicValues::eip712_root_type(),
+        solidity_struct(&source, "SwapProofPublicValues")
+    );
+}
+
+#[test]
+fn abi_round_trip() {
+    let values = sample();
+    let encoded = SwapProofPublicValues::abi_encode(&values);
+    assert_eq!(hex(&encoded), ENCODED);
+
+    let decoded = SwapProofPublicValues::abi_decode(&encoded).unwrap();
+    assert_eq!(decoded.commitment, values.commitment);
+    assert_eq!(decoded.nullifierHash, values.nullifierHash);
+    assert_eq!(decoded.tokenIn, values.tokenIn);
+    assert_eq!(decoded.tokenOut, values.tokenOut);
+    assert_eq!(decoded.amountIn, values.amountIn);
+    assert_eq!(decoded.minAmountOut, values.minAmountOut);
+    assert_eq!(decoded.merkleRoot, values.merkleRoot);
+}
+
+#[test]
+fn note_layout_extends_swap_layout() {
+    let values = sample();
+    let note_values = SwapNoteProofPublicValues {
+        commitment: values.commitment,
+        nullifierHash: values.nullifierHash,
+        tokenIn: values.tokenIn,
+        tokenOut: values.tokenOut,
+        amountIn: values.amountIn,
+        minAmountOut: values.minAmountOut,
+        merkleRoot: values.merkleRoot,
+        changeLeaf: [0x44; 32].into(),
+        outputLeaf: [0x55; 32].into(),
+        oracle: [0x66; 20].into(),
+        priceTimestamp: 1_700_000_000,
+        maxSlippageBps: 50,
+    };
+
+    // abi.decode(publicValues, (SwapProofPublicValues)) only reads the prefix
+    let encoded = SwapNoteProofPublicValues::abi_encode(&note_values);
+    assert_eq!(encoded.len(), 12 * 32);
+    assert_eq!(hex(&encoded[..7 * 32]), ENCODED);
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,66 @@
+//! Margin Proof Circuit
+//!
+//! This circuit proves a private perpetuals account meets maintenance margin at
+//! public mark prices, without revealing its positions or collateral. The account
+//! is a private note of positions committed to by accountCommitment.
+//!
+//! Public inputs: accountsRoot, collateralAsset, markPrices (asset, mark price, maintenance margin bps)
+//! Private inputs: margin account, spending_key, account_proof
+//! Proof:
+//! 1. The account is owned by the spending key
+//! 2. accountCommitment = the account's commitment, a leaf of the accounts tree under accountsRoot
+//! 3. The account holds collateralAsset and every position has exactly one mark price
+//! 4. collateral + unrealised PnL >= sum(size * markPrice * maintenanceMarginBps / 10000)
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use alloy_sol_types::SolType;
+use innocence_circuits_lib::{MarginAccount, MarginProofPublicValues, MarkPrice, MerkleProof};
+
+pub fn main() {
+    // Read private inputs
+    let account: MarginAccount = sp1_zkvm::io::read();
+    let spending_key: [u8; 32] = sp1_zkvm::io::read();
+    let account_proof: MerkleProof = sp1_zkvm::io::read();
+    
+    // Read public inputs
+    let accounts_root: [u8; 32] = sp1_zkvm::io::read();
+    let collateral_asset: u64 = sp1_zkvm::io::read();
+    let mark_prices: Vec<MarkPrice> = sp1_zkvm::io::read();
+    
+    // Step 1: Verify ownership
+    assert!(account.is_owned_by(&spending_key), "Account is not owned by the spending key");
+    
+    // Step 2: Commit to the account without revealing it, and bind it to the venue's accounts
+    let account_commitment = account.commitment();
+    assert_eq!(
+        account_proof.leaf,
+        account_commitment,
+        "Accounts tree leaf does not match the account commitment"
+    );
+    if let Err(e) = account_proof.verify(&accounts_root) {
+        panic!("Invalid accounts tree proof: {}", e);
+    }
+    
+    // Step 3: Collateral asset must match the public one
+    assert_eq!(account.collateral_asset, collateral_asset, "Collateral asset mismatch");
+    
+    // Step 4: Equity must cover maintenance margin
+    let health = match account.health(&mark_prices) {
+        Ok(health) => health,
+        Err(e) => panic!("Invalid margin account: {}", e),
+    };
+    assert!(health.is_healthy(), "Account is below maintenance margin");
+    
+    // Commit to public values
+    let public_values = MarginProofPublicValues {
+        accountCommitment: account_commitment.into(),
+        accountsRoot: accounts_root.into(),
+        collateralAsset: collateral_asset,
+        markPrices: mark_prices,
+    };
+    
+    let bytes = MarginProofPublicValues::abi_encode(&public_values);
+    sp1_zkvm::io::commit_slice(&bytes);
+}
@@ -11,8 +11,7 @@
 sp1_zkvm::entrypoint!(main);
 
 use alloy_sol_types::SolType;
-use innocence_circuits_lib::OwnershipProofPublicValues;
-use sha2::{Sha256, Digest};
+use innocence_circuits_lib::{compute_commitment, compute_nullifier_hash, OwnershipProofPublicValues};
 
 pub fn main() {
     // Read private inputs from the prover
@@ -23,10 +22,7 @@ pub fn main() {
     let expected_commitment: [u8; 32] = sp1_zkvm::io::read();
     
     // Compute commitment = hash(secret || nullifier)
-    let mut hasher = Sha256::new();
-    hasher.update(&secret);
-    hasher.update(&nullifier);
-    let computed_commitment: [u8; 32] = hasher.finalize().into();
+    let computed_commitment = compute_commitment(&secret, &nullifier);
     
     // Verify the commitment matches
     assert_eq!(
@@ -36,9 +32,7 @@ pub fn main() {
     );
     
     // Compute nullifier hash (this will be revealed to prevent double spending)
-    let mut nullifier_hasher = Sha256::new();
-    nullifier_hasher.update(&nullifier);
-    let nullifier_hash: [u8; 32] = nullifier_hasher.finalize().into();
+    let nullifier_hash = compute_nullifier_hash(&nullifier);
     
     // Commit to public values
     let public_values = OwnershipProofPublicValues {
@@ -1,15 +1,72 @@
 use sp1_build::build_program_with_args;
+use std::path::{Path, PathBuf};
+
+/// Registry of every guest program in the workspace: (vkey name, program directory, ELF name).
+/// Each entry is built here and gets a verification key from `generate-vkeys`.
+const GUEST_PROGRAMS: &[(&str, &str, &str)] = &[
+    ("ownership", "../program", "innocence-ownership-proof"),
+    ("balance", "../balance-proof", "innocence-balance-proof"),
+    ("compliance", "../compliance-proof", "innocence-compliance-proof"),
+    ("trade", "../trade-proof", "innocence-trade-proof"),
+    ("innocence", "../innocence-proof", "innocence-proof"),
+    ("withdraw", "../withdraw-proof", "innocence-withdraw-proof"),
+    ("transfer", "../transfer-proof", "innocence-transfer-proof"),
+    ("margin", "../margin-proof", "innocence-margin-proof"),
+];
 
 fn main() {
-    // Build ownership proof program
-    build_program_with_args("../program", Default::default());
-    
-    // Build balance proof program
-    build_program_with_args("../balance-proof", Default::default());
-    
-    // Build compliance proof program
-    build_program_with_args("../compliance-proof", Default::default());
-    
-    // Build trade proof program
-    build_program_with_args("../trade-proof", Default::default());
-}
\ No newline at end of file
+    println!("cargo:rerun-if-changed=../Cargo.toml");
+    check_registry();
+
+    for (_, path, _) in GUEST_PROGRAMS {
+        build_program_with_args(path, Default::default());
+    }
+
+    write_program_table();
+}
+
+/// Fail the build if a workspace member depending on sp1-zkvm is not in the registry
+fn check_registry() {
+    let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let metadata = cargo_metadata::MetadataCommand::new()
+        .manifest_path(manifest_dir.join("../Cargo.toml"))
+        .no_deps()
+        .exec()
+        .expect("Failed to read workspace metadata");
+
+    let registered: Vec<PathBuf> = GUEST_PROGRAMS
+        .iter()
+        .map(|(_, path, _)| canonical(&manifest_dir.join(path)))
+        .collect();
+
+    for package in metadata.workspace_packages() {
+        if !package.dependencies.iter().any(|dep| dep.name == "sp1-zkvm") {
+            continue;
+        }
+        let program_dir = canonical(package.manifest_path.parent().unwrap().as_std_path());
+        if !registered.contains(&program_dir) {
+            panic!(
+                "Guest program `{}` ({}) is not registered in script/build.rs GUEST_PROGRAMS",
+                package.name,
+                program_dir.display()
+            );
+        }
+    }
+}
+
+/// Emit the (vkey name, ELF) table used by `generate-vkeys`
+fn write_program_table() {
+    let mut table = String::from("/// Guest programs registered in `build.rs`: (vkey name, ELF)\n");
+    table.push_str("pub const GUEST_PROGRAMS: &[(&str, &[u8])] = &[\n");
+    for (name, _, elf) in GUEST_PROGRAMS {
+        table.push_str(&format!("    (\"{}\", sp1_sdk::include_elf!(\"{}\")),\n", name, elf));
+    }
+    table.push_str("];\n");
+
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    std::fs::write(out_dir.join("guest_programs.rs"), table).expect("Failed to write program table");
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
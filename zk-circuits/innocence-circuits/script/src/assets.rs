@@ -0,0 +1,26 @@
+//! Asset arguments
+//!
+//! Helpers for the `--asset`, `--asset-registry` and `--base-units` flags the proof
+//! generators share.
+
+use innocence_circuits_lib::{parse_units, Asset, AssetRegistry, U256};
+use std::path::Path;
+
+/// Registry from `path`, or the built-in `assets.toml`
+pub fn load_registry(path: Option<&Path>) -> Result<AssetRegistry, String> {
+    match path {
+        Some(path) => AssetRegistry::load(path).map_err(|e| e.to_string()),
+        None => Ok(AssetRegistry::builtin()),
+    }
+}
+
+/// Amount of `asset` from the command line: a decimal amount such as `1.5`, or an
+/// integer in base units with `--base-units`
+pub fn parse_amount(asset: &Asset, amount: &str, base_units: bool) -> Result<U256, String> {
+    let parsed = if base_units {
+        parse_units(amount, 0)
+    } else {
+        asset.parse_amount(amount)
+    };
+    parsed.map_err(|e| format!("Invalid {} amount {}: {}", asset.symbol, amount, e))
+}
@@ -5,10 +5,13 @@
 use alloy_sol_types::SolType;
 use clap::Parser;
 use hex;
-use innocence_circuits_lib::{BalanceProofPublicValues, MerkleProof, compute_commitment};
+use innocence_circuits_lib::{note_public_key, AssetId, BalanceProofPublicValues, IncrementalMerkleTree, Note};
+use innocence_prover::assets::{load_registry, parse_amount};
+use innocence_prover::indexer::DepositTree;
+use innocence_prover::notes::load_note;
 use sp1_sdk::{include_elf, ProverClient, SP1Stdin, HashableKey};
-use sha2::{Sha256, Digest};
 use serde_json;
+use std::path::PathBuf;
 
 /// The ELF file for the balance proof circuit
 pub const BALANCE_PROOF_ELF: &[u8] = include_elf!("innocence-balance-proof");
@@ -23,20 +26,44 @@ struct Args {
     #[arg(long)]
     prove: bool,
 
+    /// Spending key of the note
+    #[arg(long, env = "NOTE_SPENDING_KEY", hide_env_values = true)]
+    spending_key: Option<String>,
+
+    /// Blinding of the example note
     #[arg(long)]
-    secret: Option<String>,
+    blinding: Option<String>,
 
+    /// Amount blinding of the example note
     #[arg(long)]
-    nullifier: Option<String>,
-    
+    amount_blinding: Option<String>,
+
+    /// Note file (overrides --blinding, --amount-blinding, --balance and --asset)
     #[arg(long)]
-    balance: Option<u64>,
+    note: Option<PathBuf>,
     
-    #[arg(long)]
-    min_balance: Option<u64>,
+    /// Balance in asset units, e.g. `1.5`
+    #[arg(long, default_value = "1000")]
+    balance: String,
+    
+    #[arg(long, default_value = "100")]
+    min_balance: String,
     
+    /// Asset symbol or id from the asset registry
+    #[arg(long, alias = "asset-id", default_value = "HYPE")]
+    asset: String,
+
+    /// Asset registry file (defaults to the built-in assets.toml)
+    #[arg(long, env = "ASSET_REGISTRY")]
+    asset_registry: Option<PathBuf>,
+
+    /// Amounts are integers in base units rather than asset units
+    #[arg(long)]
+    base_units: bool,
+
+    /// Deposit tree state from `tree-indexer` (defaults to an example tree)
     #[arg(long)]
-    asset_id: Option<u64>,
+    tree: Option<PathBuf>,
 }
 
 fn main() {
@@ -53,8 +80,8 @@ fn main() {
     }
 
     // Setup test data
-    let secret = if let Some(s) = args.secret {
-        let bytes = hex::decode(s.trim_start_matches("0x")).expect("Invalid secret hex");
+    let spending_key = if let Some(s) = &args.spending_key {
+        let bytes = hex::decode(s.trim_start_matches("0x")).expect("Invalid spending key hex");
         let mut arr = [0u8; 32];
         arr.copy_from_slice(&bytes);
         arr
@@ -62,8 +89,8 @@ fn main() {
         [1u8; 32]
     };
 
-    let nullifier = if let Some(n) = args.nullifier {
-        let bytes = hex::decode(n.trim_start_matches("0x")).expect("Invalid nullifier hex");
+    let blinding = if let Some(b) = &args.blinding {
+        let bytes = hex::decode(b.trim_start_matches("0x")).expect("Invalid blinding hex");
         let mut arr = [0u8; 32];
         arr.copy_from_slice(&bytes);
         arr
@@ -71,44 +98,53 @@ fn main() {
         [2u8; 32]
     };
 
-    let actual_balance = args.balance.unwrap_or(1000);
-    let min_balance = args.min_balance.unwrap_or(100);
-    let asset_id = args.asset_id.unwrap_or(0); // USDC
+    let amount_blinding = if let Some(b) = &args.amount_blinding {
+        let bytes = hex::decode(b.trim_start_matches("0x")).expect("Invalid amount blinding hex");
+        let mut arr = [0u8; 32];
+        arr.copy_from_slice(&bytes);
+        arr
+    } else {
+        [3u8; 32]
+    };
+
+    let registry = load_registry(args.asset_registry.as_deref()).expect("Failed to load asset registry");
+
+    // Note whose balance is proven
+    let note = match &args.note {
+        Some(path) => load_note(path).expect("Failed to load note"),
+        None => {
+            let asset = registry.resolve(&args.asset).expect("Unknown asset");
+            Note {
+                asset_id: asset.id.0,
+                amount: parse_amount(asset, &args.balance, args.base_units).expect("Invalid balance"),
+                secret: note_public_key(&spending_key),
+                blinding,
+                amount_blinding,
+            }
+        }
+    };
+    let asset = registry.check(AssetId(note.asset_id)).expect("Note asset is not registered");
+    let asset_id = asset.id.0;
+    let actual_balance = note.amount;
+    let min_balance = parse_amount(asset, &args.min_balance, args.base_units).expect("Invalid min balance");
 
     // Compute commitment
-    let commitment = compute_commitment(&secret, &nullifier);
-    
-    // Create a simple merkle tree with our leaf
-    let mut leaf_hasher = Sha256::new();
-    leaf_hasher.update(&commitment);
-    leaf_hasher.update(&asset_id.to_le_bytes());
-    leaf_hasher.update(&actual_balance.to_le_bytes());
-    let leaf: [u8; 32] = leaf_hasher.finalize().into();
+    let commitment = note.commitment();
     
-    // For this example, create a simple merkle tree with 4 leaves
-    let dummy_leaf = [0u8; 32];
-    
-    // Build merkle tree (our leaf at position 0)
-    let mut hasher = Sha256::new();
-    hasher.update(&leaf);
-    hasher.update(&dummy_leaf);
-    let left_parent: [u8; 32] = hasher.finalize().into();
-    
-    let mut hasher = Sha256::new();
-    hasher.update(&dummy_leaf);
-    hasher.update(&dummy_leaf);
-    let right_parent: [u8; 32] = hasher.finalize().into();
-    
-    let mut hasher = Sha256::new();
-    hasher.update(&left_parent);
-    hasher.update(&right_parent);
-    let merkle_root: [u8; 32] = hasher.finalize().into();
-    
-    // Create merkle proof (path from leaf to root)
-    let merkle_proof = MerkleProof {
-        leaf,
-        path: vec![dummy_leaf, right_parent],
-        indices: vec![false, false], // left, left
+    // Merkle path for the note in the deposit tree
+    let leaf = note.leaf();
+    let (merkle_root, merkle_proof) = match &args.tree {
+        Some(path) => {
+            let state = DepositTree::load(path).expect("Failed to load deposit tree");
+            let (_, proof) = state.path(&leaf).expect("Note is not in the deposit tree");
+            (state.root(), proof)
+        }
+        None => {
+            // For this example, a depth-2 tree with our leaf at position 0 and the rest empty
+            let mut tree = IncrementalMerkleTree::new(2).expect("Invalid tree depth");
+            let leaf_index = tree.append(leaf).expect("Tree is full");
+            (tree.root(), tree.proof(leaf_index).expect("Leaf not in tree"))
+        }
     };
 
     // Setup the prover client
@@ -117,18 +153,15 @@ fn main() {
 
     // Setup the inputs
     let mut stdin = SP1Stdin::new();
-    stdin.write(&secret);
-    stdin.write(&nullifier);
-    stdin.write(&actual_balance);
+    stdin.write(&spending_key);
+    stdin.write(&note);
     // Write merkle proof components
     stdin.write(&merkle_proof.leaf);
     stdin.write(&merkle_proof.path.len());
     for node in &merkle_proof.path {
         stdin.write(node);
     }
-    for idx in &merkle_proof.indices {
-        stdin.write(idx);
-    }
+    stdin.write(&merkle_proof.leaf_index);
     stdin.write(&commitment);
     stdin.write(&merkle_root);
     stdin.write(&min_balance);
@@ -136,9 +169,9 @@ fn main() {
 
     println!("=== Balance Proof Generation ===");
     println!("Commitment: 0x{}", hex::encode(commitment));
-    println!("Asset ID: {}", asset_id);
-    println!("Actual Balance: {}", actual_balance);
-    println!("Min Balance: {}", min_balance);
+    println!("Asset: {}", asset);
+    println!("Actual Balance: {} ({} base units)", asset.format_amount(actual_balance), actual_balance);
+    println!("Min Balance: {} ({} base units)", asset.format_amount(min_balance), min_balance);
     println!("Merkle Root: 0x{}", hex::encode(merkle_root));
 
     if args.execute {
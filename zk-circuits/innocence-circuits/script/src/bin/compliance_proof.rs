@@ -1,14 +1,29 @@
 //! Innocence Protocol - Compliance Proof Generator
 //!
-//! This script generates ZK proofs for compliance verification.
+//! This script generates ZK proofs for compliance verification from a signed
+//! certificate (`--certificate`, see `issue-certificate`). A policy file
+//! (`--policy`) sets the predicates the proof evaluates on the certificate, and
+//! an issuer set file (`--issuers`) lists the compliance authorities the proof
+//! accepts without revealing which one signed.
+//!
+//! With `--credential` the proof is in credential mode instead: the input is an
+//! ES256K JWT or W3C VC-JWT from a KYC provider, checked against the provider's
+//! public JWK (`--issuer-jwk`).
 
 use alloy_sol_types::SolType;
 use clap::Parser;
 use hex;
-use innocence_circuits_lib::{ComplianceProofPublicValues, compute_commitment};
+use innocence_circuits_lib::credential::jwk_thumbprint;
+use innocence_circuits_lib::{
+    CertificateAttributes, ComplianceMode, ComplianceProofPublicValues, CredentialProofPublicValues, IssuerSet,
+    Policy, RevocationTree, compute_commitment,
+};
+use innocence_prover::compliance::{load_certificate, load_policy};
+use innocence_prover::credential::{load_credential, load_issuer_jwk, validate_credential};
+use innocence_prover::revocation::RevocationList;
 use sp1_sdk::{include_elf, ProverClient, SP1Stdin, HashableKey};
-use sha2::{Sha256, Digest};
 use serde_json;
+use std::path::PathBuf;
 
 /// The ELF file for the compliance proof circuit
 pub const COMPLIANCE_PROOF_ELF: &[u8] = include_elf!("innocence-compliance-proof");
@@ -28,9 +43,30 @@ struct Args {
 
     #[arg(long)]
     nullifier: Option<String>,
-    
+
+    /// Signed certificate for the commitment, from `issue-certificate`
+    #[arg(long, required_unless_present = "credential")]
+    certificate: Option<PathBuf>,
+
+    /// ES256K compact JWS credential (JWT or VC-JWT) whose `sub` is the commitment
+    #[arg(long, conflicts_with_all = ["certificate", "revocation_list", "issuers"], requires = "issuer_jwk")]
+    credential: Option<PathBuf>,
+
+    /// Public JWK of the credential issuer
+    #[arg(long)]
+    issuer_jwk: Option<PathBuf>,
+
+    /// JSON policy file of predicates to prove (defaults to an empty policy)
+    #[arg(long)]
+    policy: Option<PathBuf>,
+
+    /// Authority's revocation list from `revocation-tree` (defaults to an empty list)
     #[arg(long)]
-    valid_days: Option<u64>,
+    revocation_list: Option<PathBuf>,
+
+    /// TOML or JSON file of trusted issuer addresses, one of which signed the certificate
+    #[arg(long, required_unless_present = "credential")]
+    issuers: Option<PathBuf>,
 }
 
 fn main() {
@@ -47,7 +83,7 @@ fn main() {
     }
 
     // Setup test data
-    let secret = if let Some(s) = args.secret {
+    let secret = if let Some(s) = &args.secret {
         let bytes = hex::decode(s.trim_start_matches("0x")).expect("Invalid secret hex");
         let mut arr = [0u8; 32];
         arr.copy_from_slice(&bytes);
@@ -56,7 +92,7 @@ fn main() {
         [1u8; 32]
     };
 
-    let nullifier = if let Some(n) = args.nullifier {
+    let nullifier = if let Some(n) = &args.nullifier {
         let bytes = hex::decode(n.trim_start_matches("0x")).expect("Invalid nullifier hex");
         let mut arr = [0u8; 32];
         arr.copy_from_slice(&bytes);
@@ -68,63 +104,44 @@ fn main() {
     // Compute commitment
     let commitment = compute_commitment(&secret, &nullifier);
     
-    // Mock compliance authority (in production, this would be the real authority address)
-    let compliance_authority = [0x5B, 0xd2, 0xF3, 0x29, 0xC5, 0x08, 0x60, 0x36, 0x6c, 0x0E, 
-                                0x6D, 0x3b, 0x42, 0x27, 0xa4, 0x22, 0xB6, 0x6A, 0xD2, 0x03]; // Your address
-    
-    // Certificate validity
     let current_timestamp = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
         .as_secs();
-    let valid_days = args.valid_days.unwrap_or(365);
-    let valid_until = current_timestamp + (valid_days * 24 * 60 * 60);
-    
-    // Create certificate data
-    let mut certificate_data = Vec::new();
-    certificate_data.extend_from_slice(&commitment);
-    certificate_data.extend_from_slice(&valid_until.to_le_bytes());
-    certificate_data.extend_from_slice(b"USER_ID_12345"); // Mock user data
-    
-    // Create mock signature (in production, this would be a real ECDSA signature)
-    let mut authority_signature = [0u8; 65];
-    authority_signature[0] = 27; // Recovery ID
-    authority_signature[1..33].copy_from_slice(&[1u8; 32]); // r
-    authority_signature[33..65].copy_from_slice(&[2u8; 32]); // s
+
+    // Policy the venue requires
+    let policy = match &args.policy {
+        Some(path) => load_policy(path).expect("Failed to load policy"),
+        None => Policy::default(),
+    };
 
     // Setup the prover client
     // Use local proving (no network access needed)
     let client = ProverClient::from_env();
 
     // Setup the inputs
+    let mode = if args.credential.is_some() { ComplianceMode::Credential } else { ComplianceMode::Certificate };
     let mut stdin = SP1Stdin::new();
-    stdin.write(&secret);
-    stdin.write(&nullifier);
-    stdin.write(&certificate_data);
-    // Write signature components separately
-    stdin.write(&authority_signature[0]); // v
-    let r: [u8; 32] = authority_signature[1..33].try_into().unwrap();
-    stdin.write(&r); // r
-    let s: [u8; 32] = authority_signature[33..65].try_into().unwrap();
-    stdin.write(&s); // s
-    stdin.write(&commitment);
-    stdin.write(&compliance_authority);
-    stdin.write(&valid_until);
-    stdin.write(&current_timestamp);
-
-    // Compute certificate hash
-    let mut cert_hasher = Sha256::new();
-    cert_hasher.update(&certificate_data);
-    let certificate_hash: [u8; 32] = cert_hasher.finalize().into();
+    stdin.write(&mode);
 
     println!("=== Compliance Proof Generation ===");
     println!("Commitment: 0x{}", hex::encode(commitment));
-    println!("Authority: 0x{}", hex::encode(compliance_authority));
-    println!("Valid Until: {} ({})", valid_until, 
-        chrono::DateTime::<chrono::Utc>::from_timestamp(valid_until as i64, 0)
-            .unwrap()
-            .format("%Y-%m-%d %H:%M:%S UTC"));
-    println!("Certificate Hash: 0x{}", hex::encode(certificate_hash));
+
+    let attributes = match mode {
+        ComplianceMode::Certificate => {
+            write_certificate_inputs(&args, &mut stdin, secret, nullifier, commitment, current_timestamp)
+        }
+        ComplianceMode::Credential => {
+            write_credential_inputs(&args, &mut stdin, secret, nullifier, commitment, current_timestamp)
+        }
+    };
+    stdin.write(&policy);
+    let predicates_satisfied = policy.evaluate(&attributes, current_timestamp);
+
+    println!("Attributes: jurisdiction {} accreditation {:?} KYC tier {:?}",
+        attributes.jurisdiction, attributes.accreditation, attributes.kyc_tier);
+    println!("Policy Hash: 0x{} ({} predicates)", hex::encode(policy.hash()), policy.predicates.len());
+    println!("Predicates Satisfied: {}", predicates_satisfied);
 
     if args.execute {
         // Execute the program
@@ -132,12 +149,30 @@ fn main() {
         println!("\nProgram executed successfully!");
 
         // Read the output
-        let decoded = ComplianceProofPublicValues::abi_decode(output.as_slice()).unwrap();
         println!("\nPublic outputs:");
-        println!("  Commitment: 0x{}", hex::encode(decoded.commitment));
-        println!("  Authority: 0x{}", hex::encode(decoded.complianceAuthority));
-        println!("  Valid Until: {}", decoded.validUntil);
-        println!("  Certificate Hash: 0x{}", hex::encode(decoded.certificateHash));
+        match mode {
+            ComplianceMode::Certificate => {
+                let decoded = ComplianceProofPublicValues::abi_decode(output.as_slice()).unwrap();
+                println!("  Mode: {}", decoded.mode);
+                println!("  Commitment: 0x{}", hex::encode(decoded.commitment));
+                println!("  Issuer Set Root: 0x{}", hex::encode(decoded.issuerSetRoot));
+                println!("  Valid Until: {}", decoded.validUntil);
+                println!("  Timestamp: {}", decoded.timestamp);
+                println!("  Certificate Hash: 0x{}", hex::encode(decoded.certificateHash));
+                println!("  Revocation Root: 0x{}", hex::encode(decoded.revocationRoot));
+                println!("  Predicate Hash: 0x{}", hex::encode(decoded.predicateHash));
+                println!("  Predicates Satisfied: {}", decoded.predicatesSatisfied);
+            }
+            ComplianceMode::Credential => {
+                let decoded = CredentialProofPublicValues::abi_decode(output.as_slice()).unwrap();
+                println!("  Mode: {}", decoded.mode);
+                println!("  Commitment: 0x{}", hex::encode(decoded.commitment));
+                println!("  Issuer Key Hash: 0x{}", hex::encode(decoded.issuerKeyHash));
+                println!("  Timestamp: {}", decoded.timestamp);
+                println!("  Predicate Hash: 0x{}", hex::encode(decoded.predicateHash));
+                println!("  Predicates Satisfied: {}", decoded.predicatesSatisfied);
+            }
+        }
 
         // Record the number of cycles executed
         println!("\nNumber of cycles: {}", report.total_instruction_count());
@@ -185,7 +220,9 @@ fn main() {
             "proof": proof,
             "rawBytes": format!("0x{}", hex::encode(&proof_bytes)),
             "publicValues": format!("0x{}", hex::encode(&proof.public_values.as_slice())),
-            "vkey": format!("0x{}", hex::encode(&vk.bytes32()))
+            "vkey": format!("0x{}", hex::encode(&vk.bytes32())),
+            "predicateHash": format!("0x{}", hex::encode(policy.hash())),
+            "predicatesSatisfied": predicates_satisfied
         });
 
         // Save the proof to a file
@@ -194,4 +231,118 @@ fn main() {
             .expect("Failed to write proof");
         println!("✓ Proof saved to: {}", proof_path);
     }
-}
\ No newline at end of file
+}
+
+/// Load and check the certificate, then write the certificate mode inputs
+/// (all but the policy)
+fn write_certificate_inputs(
+    args: &Args,
+    stdin: &mut SP1Stdin,
+    secret: [u8; 32],
+    nullifier: [u8; 32],
+    commitment: [u8; 32],
+    current_timestamp: u64,
+) -> CertificateAttributes {
+    // Signed certificate for the commitment
+    let signed = load_certificate(args.certificate.as_ref().unwrap()).expect("Failed to load certificate");
+    if let Err(e) = signed.verify() {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+    let certificate = &signed.certificate;
+    if certificate.subject != commitment {
+        eprintln!("Error: certificate subject 0x{} is not the commitment", hex::encode(certificate.subject));
+        std::process::exit(1);
+    }
+    let compliance_authority = certificate.issuer;
+    let attributes = certificate.attributes;
+    let valid_until = certificate.expires_at;
+    let certificate_data = certificate.encode();
+    let certificate_hash = certificate.hash();
+
+    if !certificate.is_valid_at(current_timestamp) {
+        eprintln!("Error: certificate is not valid now ({} to {})", certificate.issued_at, certificate.expires_at);
+        std::process::exit(1);
+    }
+
+    // The certificate must not be on the authority's revocation list
+    let revocation_tree = match &args.revocation_list {
+        Some(path) => RevocationList::load(path)
+            .and_then(|list| list.tree())
+            .expect("Failed to load revocation list"),
+        None => RevocationTree::new(&[]),
+    };
+    let revocation_root = revocation_tree.root();
+    let revocation_proof = revocation_tree
+        .non_membership_proof(&certificate_hash)
+        .expect("Certificate is revoked");
+
+    // The signing authority must be one of the trusted issuers
+    let issuer_set = IssuerSet::load(args.issuers.as_ref().unwrap()).expect("Failed to load issuer set");
+    let issuer_set_root = issuer_set.root();
+    let issuer_proof = match issuer_set.proof(&compliance_authority) {
+        Some(proof) => proof,
+        None => {
+            eprintln!("Error: authority 0x{} is not in the issuer set", hex::encode(compliance_authority));
+            std::process::exit(1);
+        }
+    };
+
+    stdin.write(&secret);
+    stdin.write(&nullifier);
+    stdin.write(&certificate_data);
+    // Write signature components separately
+    stdin.write(&signed.v);
+    stdin.write(&signed.r);
+    stdin.write(&signed.s);
+    stdin.write(&revocation_proof);
+    stdin.write(&issuer_proof);
+    stdin.write(&commitment);
+    stdin.write(&issuer_set_root);
+    stdin.write(&current_timestamp);
+    stdin.write(&revocation_root);
+
+    println!("Authority: 0x{}", hex::encode(compliance_authority));
+    println!("Issuer Set Root: 0x{} ({} issuers)", hex::encode(issuer_set_root), issuer_set.len());
+    println!("Valid Until: {} ({})", valid_until, 
+        chrono::DateTime::<chrono::Utc>::from_timestamp(valid_until as i64, 0)
+            .unwrap()
+            .format("%Y-%m-%d %H:%M:%S UTC"));
+    println!("Certificate Hash: 0x{}", hex::encode(certificate_hash));
+    println!("Revocation Root: 0x{} ({} revoked)", hex::encode(revocation_root), revocation_tree.len());
+
+    attributes
+}
+
+/// Load and validate the credential, then write the credential mode inputs
+/// (all but the policy)
+fn write_credential_inputs(
+    args: &Args,
+    stdin: &mut SP1Stdin,
+    secret: [u8; 32],
+    nullifier: [u8; 32],
+    commitment: [u8; 32],
+    current_timestamp: u64,
+) -> CertificateAttributes {
+    let token = load_credential(args.credential.as_ref().unwrap()).expect("Failed to load credential");
+    let issuer_key = load_issuer_jwk(args.issuer_jwk.as_ref().unwrap()).expect("Failed to load issuer JWK");
+    let credential = validate_credential(&token, &issuer_key, &commitment, current_timestamp).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+    let issuer_key_hash = jwk_thumbprint(&issuer_key).expect("Invalid issuer key");
+
+    stdin.write(&secret);
+    stdin.write(&nullifier);
+    stdin.write(&token);
+    stdin.write(&issuer_key);
+    stdin.write(&commitment);
+    stdin.write(&current_timestamp);
+
+    let claims = &credential.claims;
+    println!("Issuer: {}", claims.issuer.as_deref().unwrap_or("(no iss claim)"));
+    println!("Issuer Key Hash: 0x{}", hex::encode(issuer_key_hash));
+    println!("Expires At: {}", claims.expires_at);
+
+    claims.attributes
+}
@@ -3,16 +3,13 @@
 //! This script generates the verification keys for each proof type.
 
 use clap::Parser;
-use sp1_sdk::{include_elf, ProverClient, HashableKey};
+use sp1_sdk::{ProverClient, HashableKey};
 use std::fs;
 use std::path::Path;
 use serde_json::json;
 
-/// The ELF files for each proof circuit
-pub const OWNERSHIP_PROOF_ELF: &[u8] = include_elf!("innocence-ownership-proof");
-pub const BALANCE_PROOF_ELF: &[u8] = include_elf!("innocence-balance-proof");
-pub const COMPLIANCE_PROOF_ELF: &[u8] = include_elf!("innocence-compliance-proof");
-pub const TRADE_PROOF_ELF: &[u8] = include_elf!("innocence-trade-proof");
+// The ELF files for each proof circuit, generated from the registry in build.rs
+include!(concat!(env!("OUT_DIR"), "/guest_programs.rs"));
 
 /// The arguments for the command
 #[derive(Parser, Debug)]
@@ -38,17 +35,10 @@ fn main() {
 
     println!("=== Generating SP1 Verification Keys ===");
 
-    // Generate vkeys for each proof type
-    let proof_types = vec![
-        ("ownership", OWNERSHIP_PROOF_ELF),
-        ("balance", BALANCE_PROOF_ELF),
-        ("compliance", COMPLIANCE_PROOF_ELF),
-        ("trade", TRADE_PROOF_ELF),
-    ];
-
     let mut vkeys = json!({});
 
-    for (name, elf) in &proof_types {
+    // Generate vkeys for each proof type
+    for (name, elf) in GUEST_PROGRAMS {
         println!("\nGenerating {} verification key...", name);
         
         // Setup the program
@@ -77,7 +67,7 @@ fn main() {
     sol_content.push_str("pragma solidity ^0.8.19;\n\n");
     sol_content.push_str("library InnocenceVerificationKeys {\n");
     
-    for (name, _) in &proof_types {
+    for (name, _) in GUEST_PROGRAMS {
         let vkey = &vkeys[name]["vkey"];
         sol_content.push_str(&format!(
             "    bytes32 public constant {}_VKEY = {};\n",
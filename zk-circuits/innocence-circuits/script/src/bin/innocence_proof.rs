@@ -1,16 +1,37 @@
 //! Innocence Protocol - Innocence Proof Generator
 //!
-//! This script generates ZK proofs that an address is NOT on the sanctions list
+//! This script generates ZK proofs that an address is NOT on the sanctions list,
+//! or with `--mode allowlist` that it IS on an allowlist (`--allowlist`, a
+//! snapshot built by `sanctions-tree build` from the list of approved addresses)
 
 use alloy_sol_types::SolType;
 use clap::Parser;
 use hex;
+use innocence_circuits_lib::{AllowlistedAddress, InnocenceMode, InnocenceProofPublicValues, SanctionsTree};
+use innocence_prover::sanctions::SanctionsSnapshot;
 use sp1_sdk::{include_elf, ProverClient, SP1Stdin, HashableKey};
 use serde_json;
 
 /// The ELF file for the innocence proof circuit
 pub const INNOCENCE_PROOF_ELF: &[u8] = include_elf!("innocence-proof");
 
+/// Example sanctioned addresses (OFAC list), used when no snapshot is provided
+const DEFAULT_SANCTIONED_ADDRESSES: [&str; 3] = [
+    // Tornado Cash addresses
+    "8589427373D6D84E98730D7795D8f6f8731FDA16",
+    "722122dF12D4e14e13Ac3b6895a86e84145b6967",
+    "DD4c48C0B24039969fC16D1cdF626eaB821d3384",
+];
+
+/// List the depositor is checked against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum Mode {
+    /// Prove the depositor is not on the sanctions list
+    Sanctions,
+    /// Prove the depositor is on the allowlist
+    Allowlist,
+}
+
 /// The arguments for the command
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -23,6 +44,17 @@ struct Args {
 
     #[arg(long)]
     depositor: Option<String>, // Ethereum address to check
+
+    #[arg(long, value_enum, default_value_t = Mode::Sanctions)]
+    mode: Mode,
+
+    /// Sanctions snapshot produced by `sanctions-tree build`
+    #[arg(long, conflicts_with = "allowlist")]
+    snapshot: Option<std::path::PathBuf>,
+
+    /// Allowlist snapshot produced by `sanctions-tree build`, required in allowlist mode
+    #[arg(long, required_if_eq("mode", "allowlist"))]
+    allowlist: Option<std::path::PathBuf>,
 }
 
 fn main() {
@@ -48,9 +80,15 @@ fn main() {
     let mut depositor = [0u8; 20];
     depositor.copy_from_slice(&depositor_bytes);
 
-    // Mock sanctions root (in production, this would come from an oracle)
-    let sanctions_root = [0u8; 32]; // Empty root for now
-    
+    if args.mode == Mode::Sanctions && args.allowlist.is_some() {
+        eprintln!("Error: --allowlist requires --mode allowlist");
+        std::process::exit(1);
+    }
+    if args.mode == Mode::Allowlist && args.snapshot.is_some() {
+        eprintln!("Error: --snapshot requires --mode sanctions");
+        std::process::exit(1);
+    }
+
     let timestamp = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
@@ -62,13 +100,73 @@ fn main() {
 
     // Setup the inputs
     let mut stdin = SP1Stdin::new();
-    stdin.write(&depositor);
-    stdin.write(&sanctions_root);
-    stdin.write(&timestamp);
 
     println!("=== Innocence Proof Generation ===");
     println!("Depositor: 0x{}", hex::encode(depositor));
-    println!("Sanctions Root: 0x{}", hex::encode(sanctions_root));
+
+    match args.mode {
+        Mode::Allowlist => {
+            // Build the allowlist tree and the membership witness for the depositor
+            // (clap requires --allowlist in this mode)
+            let path = args.allowlist.as_ref().unwrap();
+            let allowlist_tree = SanctionsSnapshot::load(path)
+                .and_then(|snapshot| snapshot.allowlist_tree())
+                .expect("Failed to load allowlist snapshot");
+            let allowlist_root = allowlist_tree.root();
+
+            let membership = match allowlist_tree.membership_proof(&AllowlistedAddress(depositor)) {
+                Some(proof) => proof,
+                None => {
+                    eprintln!("Error: 0x{} is not on the allowlist", hex::encode(depositor));
+                    std::process::exit(1);
+                }
+            };
+
+            stdin.write(&InnocenceMode::Allowlist);
+            stdin.write(&depositor);
+            stdin.write(&allowlist_root);
+            stdin.write(&timestamp);
+            stdin.write(&membership);
+
+            println!("Mode: allowlist");
+            println!("Allowlist Root: 0x{}", hex::encode(allowlist_root));
+            println!("Allowlisted Addresses: {}", allowlist_tree.len());
+        }
+        Mode::Sanctions => {
+            // Build the sanctions tree and the non-membership witness for the depositor
+            let sanctions_tree = match &args.snapshot {
+                Some(path) => SanctionsSnapshot::load(path)
+                    .and_then(|snapshot| snapshot.tree())
+                    .expect("Failed to load sanctions snapshot"),
+                None => {
+                    let sanctioned: Vec<[u8; 20]> = DEFAULT_SANCTIONED_ADDRESSES
+                        .iter()
+                        .map(|a| innocence_prover::parse_address(a).unwrap())
+                        .collect();
+                    SanctionsTree::new(&sanctioned)
+                }
+            };
+            let sanctions_root = sanctions_tree.root();
+
+            let non_membership = match sanctions_tree.non_membership_proof(&depositor) {
+                Some(proof) => proof,
+                None => {
+                    eprintln!("Error: 0x{} is on the sanctions list", hex::encode(depositor));
+                    std::process::exit(1);
+                }
+            };
+
+            stdin.write(&InnocenceMode::Sanctions);
+            stdin.write(&depositor);
+            stdin.write(&sanctions_root);
+            stdin.write(&timestamp);
+            stdin.write(&non_membership);
+
+            println!("Mode: sanctions");
+            println!("Sanctions Root: 0x{}", hex::encode(sanctions_root));
+            println!("Sanctioned Addresses: {}", sanctions_tree.len());
+        }
+    }
     println!("Timestamp: {}", timestamp);
 
     if args.execute {
@@ -76,11 +174,21 @@ fn main() {
         let (output, report) = client.execute(INNOCENCE_PROOF_ELF, &stdin).run().unwrap();
         println!("\nProgram executed successfully!");
 
-        // Read the output (depositor, sanctions_root, timestamp, is_innocent)
-        let output_bytes = output.as_slice();
-        let is_innocent = output_bytes[output_bytes.len() - 1] != 0;
+        // Read the output
+        let decoded = InnocenceProofPublicValues::abi_decode(output.as_slice()).unwrap();
+        println!("\nPublic outputs:");
+        println!("  Mode: {}", decoded.mode);
+        println!("  Depositor: 0x{}", hex::encode(decoded.depositor));
+        println!("  List Root: 0x{}", hex::encode(decoded.listRoot));
+        println!("  Timestamp: {}", decoded.timestamp);
         
-        println!("\nResult: Address is {}", if is_innocent { "INNOCENT ✅" } else { "SANCTIONED ❌" });
+        let verdict = match (decoded.isInnocent, args.mode) {
+            (true, Mode::Sanctions) => "INNOCENT ✅",
+            (true, Mode::Allowlist) => "ALLOWLISTED ✅",
+            (false, Mode::Sanctions) => "SANCTIONED ❌",
+            (false, Mode::Allowlist) => "NOT ALLOWLISTED ❌",
+        };
+        println!("\nResult: Address is {}", verdict);
         println!("Number of cycles: {}", report.total_instruction_count());
     } else {
         // Setup the program for proving
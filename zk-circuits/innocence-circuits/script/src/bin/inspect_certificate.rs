@@ -0,0 +1,88 @@
+//! Innocence Protocol - Compliance Certificate Inspector
+//!
+//! Decodes a certificate file, verifies the issuer's signature and reports
+//! whether the certificate is currently valid. Exits non-zero if the signature
+//! does not verify or the issuer is not in `--issuers`.
+
+use clap::Parser;
+use innocence_circuits_lib::IssuerSet;
+use innocence_prover::compliance::load_certificate;
+use std::path::PathBuf;
+
+/// The arguments for the command
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Certificate file from `issue-certificate`
+    certificate: PathBuf,
+
+    /// Also check the issuer is in this issuer set
+    #[arg(long)]
+    issuers: Option<PathBuf>,
+}
+
+fn format_time(timestamp: u64) -> String {
+    chrono::DateTime::<chrono::Utc>::from_timestamp(timestamp as i64, 0)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| "out of range".to_string())
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let signed = load_certificate(&args.certificate).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+    let certificate = &signed.certificate;
+    let attributes = &certificate.attributes;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    println!("=== Compliance Certificate ===");
+    println!("Version: {}", certificate.version);
+    println!("Issuer: 0x{}", hex::encode(certificate.issuer));
+    println!("Subject: 0x{}", hex::encode(certificate.subject));
+    println!("Issued At: {} ({})", certificate.issued_at, format_time(certificate.issued_at));
+    println!("Expires At: {} ({})", certificate.expires_at, format_time(certificate.expires_at));
+    println!("Jurisdiction: {}", attributes.jurisdiction);
+    println!("Accreditation: {:?}", attributes.accreditation);
+    println!("KYC Tier: {:?}", attributes.kyc_tier);
+    println!("Date of Birth: {}", attributes.date_of_birth);
+    println!("Certificate Hash: 0x{}", hex::encode(certificate.hash()));
+
+    let mut ok = true;
+    match signed.verify() {
+        Ok(()) => println!("✓ Signature verified"),
+        Err(e) => {
+            println!("✗ {}", e);
+            ok = false;
+        }
+    }
+
+    if certificate.is_valid_at(now) {
+        println!("✓ Currently valid");
+    } else if now < certificate.issued_at {
+        println!("✗ Not yet valid");
+    } else {
+        println!("✗ Expired");
+    }
+
+    if let Some(path) = &args.issuers {
+        let issuers = IssuerSet::load(path).expect("Failed to load issuer set");
+        match issuers.issuers().iter().find(|issuer| issuer.address == certificate.issuer) {
+            Some(issuer) if issuer.name.is_empty() => println!("✓ Issuer is in the issuer set"),
+            Some(issuer) => println!("✓ Issuer is in the issuer set ({})", issuer.name),
+            None => {
+                println!("✗ Issuer is not in the issuer set");
+                ok = false;
+            }
+        }
+    }
+
+    if !ok {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,88 @@
+//! Innocence Protocol - Compliance Certificate Issuer
+//!
+//! Issues a signed compliance certificate for a subject commitment, as the
+//! compliance authority whose key is in `--key-file`. The certificate file is the
+//! input of `compliance-proof --certificate`.
+
+use clap::Parser;
+use innocence_circuits_lib::{AccreditationLevel, CertificateAttributes, CivilDate, Jurisdiction, KycTier};
+use innocence_prover::compliance::{issue_certificate, load_authority_key, save_certificate};
+use innocence_prover::parse_bytes32;
+use std::path::PathBuf;
+
+/// The arguments for the command
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// File holding the authority's secp256k1 private key in hex
+    #[arg(long)]
+    key_file: PathBuf,
+
+    /// Commitment of the certified user's deposit
+    #[arg(long)]
+    subject: String,
+
+    /// ISO 3166-1 alpha-2 jurisdiction of the certified user
+    #[arg(long)]
+    jurisdiction: Jurisdiction,
+
+    /// none, accredited, qualified or institutional
+    #[arg(long, default_value = "none")]
+    accreditation: AccreditationLevel,
+
+    /// none, basic, standard or enhanced
+    #[arg(long, default_value = "standard")]
+    kyc_tier: KycTier,
+
+    /// Date of birth of the certified user, YYYY-MM-DD
+    #[arg(long)]
+    date_of_birth: CivilDate,
+
+    /// Unix time the certificate is valid from (defaults to now)
+    #[arg(long)]
+    issued_at: Option<u64>,
+
+    #[arg(long, default_value = "365")]
+    valid_days: u64,
+
+    #[arg(long, default_value = "certificate.json")]
+    output: PathBuf,
+}
+
+fn main() {
+    dotenv::dotenv().ok();
+    let args = Args::parse();
+
+    let key = load_authority_key(&args.key_file).expect("Failed to load authority key");
+    let subject = parse_bytes32(&args.subject).expect("Subject must be a 32-byte commitment");
+    let issued_at = args.issued_at.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    });
+    let expires_at = args
+        .valid_days
+        .checked_mul(24 * 60 * 60)
+        .and_then(|validity| issued_at.checked_add(validity))
+        .expect("Validity period overflows");
+
+    let attributes = CertificateAttributes {
+        jurisdiction: args.jurisdiction,
+        accreditation: args.accreditation,
+        kyc_tier: args.kyc_tier,
+        date_of_birth: args.date_of_birth,
+    };
+    let signed = issue_certificate(&key, subject, issued_at, expires_at, attributes).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+    save_certificate(&signed, &args.output).expect("Failed to write certificate");
+
+    let certificate = &signed.certificate;
+    println!("Issuer: 0x{}", hex::encode(certificate.issuer));
+    println!("Subject: 0x{}", hex::encode(certificate.subject));
+    println!("Valid: {} to {}", certificate.issued_at, certificate.expires_at);
+    println!("Certificate Hash: 0x{}", hex::encode(certificate.hash()));
+    println!("✓ Certificate saved to: {}", args.output.display());
+}
@@ -0,0 +1,227 @@
+//! Innocence Protocol - Margin Proof Generator
+//!
+//! This script generates ZK proofs that a private perpetuals account meets
+//! maintenance margin at public mark prices. The account comes from a positions
+//! file and must be in the venue's accounts tree (`--accounts`); mark prices are
+//! given as `--mark UETH=3100`.
+
+use alloy_sol_types::SolType;
+use clap::Parser;
+use hex;
+use innocence_circuits_lib::tree::DEFAULT_TREE_DEPTH;
+use innocence_circuits_lib::{note_public_key, AssetId, IncrementalMerkleTree, MarginProofPublicValues, MarkPrice};
+use innocence_prover::assets::load_registry;
+use innocence_prover::margin::{load_accounts_tree, parse_mark, PositionsFile};
+use innocence_prover::oracle::format_price;
+use innocence_prover::parse_bytes32;
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin, HashableKey};
+use serde_json;
+use std::path::PathBuf;
+
+/// The ELF file for the margin proof circuit
+pub const MARGIN_PROOF_ELF: &[u8] = include_elf!("innocence-margin-proof");
+
+/// Depth of the example accounts tree used without --accounts
+const EXAMPLE_TREE_DEPTH: usize = 2;
+
+/// The arguments for the command
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[arg(long)]
+    execute: bool,
+
+    #[arg(long)]
+    prove: bool,
+
+    /// Positions file describing the margin account
+    #[arg(long)]
+    positions: PathBuf,
+
+    /// Spending key owning the account
+    #[arg(long, env = "NOTE_SPENDING_KEY", hide_env_values = true)]
+    spending_key: String,
+
+    /// Mark price as ASSET=PRICE in collateral units per asset unit; one per position asset
+    #[arg(long = "mark")]
+    marks: Vec<String>,
+
+    /// Maintenance margin applied to every mark price
+    #[arg(long, default_value_t = 500)]
+    maintenance_margin_bps: u32,
+
+    /// Asset registry file (defaults to the built-in assets.toml)
+    #[arg(long, env = "ASSET_REGISTRY")]
+    asset_registry: Option<PathBuf>,
+
+    /// The venue's account commitments as a JSON array (defaults to an example tree)
+    #[arg(long)]
+    accounts: Option<PathBuf>,
+
+    /// Depth of the venue's accounts tree
+    #[arg(long, default_value_t = DEFAULT_TREE_DEPTH)]
+    accounts_depth: usize,
+}
+
+fn main() {
+    // Setup the logger
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    // Parse the command line arguments
+    let args = Args::parse();
+
+    if args.execute == args.prove {
+        eprintln!("Error: You must specify either --execute or --prove");
+        std::process::exit(1);
+    }
+
+    let spending_key = parse_bytes32(&args.spending_key).expect("Invalid spending key hex");
+    let registry = load_registry(args.asset_registry.as_deref()).expect("Failed to load asset registry");
+
+    // Private margin account
+    let positions = PositionsFile::load(&args.positions).expect("Failed to load positions file");
+    let account = positions
+        .to_account(&registry, &note_public_key(&spending_key))
+        .expect("Invalid positions file");
+    let collateral = registry
+        .check(AssetId(account.collateral_asset))
+        .expect("Collateral asset is not registered");
+
+    // Public mark prices
+    let mark_prices: Vec<MarkPrice> = args
+        .marks
+        .iter()
+        .map(|mark| {
+            parse_mark(&registry, account.collateral_asset, mark, args.maintenance_margin_bps)
+                .expect("Invalid mark price")
+        })
+        .collect();
+
+    // Fail early rather than in the circuit
+    let health = account.health(&mark_prices).expect("Invalid margin account");
+
+    // Merkle path for the account in the venue's accounts tree
+    let account_commitment = account.commitment();
+    let accounts = match &args.accounts {
+        Some(path) => load_accounts_tree(path, args.accounts_depth).expect("Failed to load accounts tree"),
+        None => IncrementalMerkleTree::from_leaves(EXAMPLE_TREE_DEPTH, &[account_commitment]).expect("Invalid example tree"),
+    };
+    let accounts_root = accounts.root();
+    let index = accounts.position(&account_commitment).expect("Account is not in the accounts tree");
+    let account_proof = accounts.proof(index).expect("Leaf not in tree");
+
+    // Setup the prover client
+    // Use local proving (no network access needed)
+    let client = ProverClient::from_env();
+
+    // Setup the inputs
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&account);
+    stdin.write(&spending_key);
+    stdin.write(&account_proof);
+    stdin.write(&accounts_root);
+    stdin.write(&account.collateral_asset);
+    stdin.write(&mark_prices);
+
+    println!("=== Margin Proof Generation ===");
+    println!("Account Commitment: 0x{}", hex::encode(account_commitment));
+    println!("Accounts Root: 0x{}", hex::encode(accounts_root));
+    if positions.blinding.is_none() {
+        println!("Blinding: 0x{} (add it to the positions file to keep this commitment)", hex::encode(account.blinding));
+    }
+    println!("Collateral: {} {}", collateral.format_amount(account.collateral), collateral.symbol);
+    for position in &account.positions {
+        let asset = registry.check(AssetId(position.asset_id)).expect("Position asset is not registered");
+        println!(
+            "Position: {:?} {} {} @ {}",
+            position.side,
+            asset.format_amount(position.size),
+            asset.symbol,
+            format_price(asset, collateral, position.entry_price)
+        );
+    }
+    for mark in &mark_prices {
+        let asset = registry.check(AssetId(mark.assetId)).expect("Mark price asset is not registered");
+        println!("Mark Price: {} {} ({} bps maintenance)", asset.symbol, format_price(asset, collateral, mark.price), mark.maintenanceMarginBps);
+    }
+    match health.equity() {
+        Some(equity) => println!("Equity: {} {}", collateral.format_amount(equity), collateral.symbol),
+        None => println!("Equity: negative"),
+    }
+    println!("Maintenance Margin: {} {}", collateral.format_amount(health.maintenance_margin()), collateral.symbol);
+    assert!(health.is_healthy(), "Account is below maintenance margin");
+
+    if args.execute {
+        // Execute the program
+        let (output, report) = client.execute(MARGIN_PROOF_ELF, &stdin).run().unwrap();
+        println!("\nProgram executed successfully!");
+
+        // Read the output
+        let decoded = MarginProofPublicValues::abi_decode(output.as_slice()).unwrap();
+        println!("\nPublic outputs:");
+        println!("  Account Commitment: 0x{}", hex::encode(decoded.accountCommitment));
+        println!("  Accounts Root: 0x{}", hex::encode(decoded.accountsRoot));
+        println!("  Collateral Asset: {}", decoded.collateralAsset);
+        for mark in &decoded.markPrices {
+            println!("  Mark Price: asset {} price {} margin {} bps", mark.assetId, mark.price, mark.maintenanceMarginBps);
+        }
+
+        // Record the number of cycles executed
+        println!("\nNumber of cycles: {}", report.total_instruction_count());
+    } else {
+        // Setup the program for proving
+        println!("\nSetting up proving keys...");
+        let (pk, vk) = client.setup(MARGIN_PROOF_ELF);
+
+        // Generate the proof
+        let use_groth16 = std::env::var("USE_GROTH16").unwrap_or_else(|_| "false".to_string()) == "true";
+
+        let proof = if use_groth16 {
+            println!("Generating Groth16 proof...");
+            client
+                .prove(&pk, &stdin)
+                .groth16()
+                .run()
+                .expect("failed to generate proof")
+        } else {
+            println!("Generating Core proof...");
+            client
+                .prove(&pk, &stdin)
+                .run()
+                .expect("failed to generate proof")
+        };
+
+        println!("✓ Successfully generated {} proof!", if use_groth16 { "Groth16" } else { "Core" });
+
+        // Verify the proof
+        client.verify(&proof, &vk).expect("failed to verify proof");
+        println!("✓ Successfully verified proof!");
+
+        // Get the raw proof bytes for the verifier (if supported)
+        let proof_bytes = if use_groth16 {
+            proof.bytes()
+        } else {
+            // For Core proofs, we'll use a placeholder
+            // In production, you must use Groth16 or PLONK for on-chain verification
+            vec![0u8; 32]
+        };
+        println!("Proof bytes (for verifier): 0x{}", hex::encode(&proof_bytes));
+
+        // Create proof JSON with both SP1 proof format and raw bytes
+        let proof_json = serde_json::json!({
+            "proof": proof,
+            "rawBytes": format!("0x{}", hex::encode(&proof_bytes)),
+            "publicValues": format!("0x{}", hex::encode(&proof.public_values.as_slice())),
+            "vkey": format!("0x{}", hex::encode(&vk.bytes32())),
+            "accountCommitment": format!("0x{}", hex::encode(account_commitment)),
+            "accountsRoot": format!("0x{}", hex::encode(accounts_root))
+        });
+
+        // Save the proof to a file
+        let proof_path = "margin_proof.json";
+        std::fs::write(proof_path, serde_json::to_string_pretty(&proof_json).unwrap())
+            .expect("Failed to write proof");
+        println!("✓ Proof saved to: {}", proof_path);
+    }
+}
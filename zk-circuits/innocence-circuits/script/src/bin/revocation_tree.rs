@@ -0,0 +1,141 @@
+//! Innocence Protocol - Revocation Tree Builder
+//!
+//! Maintains a compliance authority's certificate revocation list, prints the
+//! `revocationRoot` for the contracts, and emits non-membership witnesses for the
+//! compliance proof.
+
+use clap::{Parser, Subcommand};
+use innocence_prover::parse_bytes32;
+use innocence_prover::revocation::RevocationList;
+use std::path::{Path, PathBuf};
+
+/// The arguments for the command
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Create an empty revocation list
+    Init {
+        #[arg(long, default_value = "revocation_list.json")]
+        list: PathBuf,
+    },
+    /// Add certificate hashes to the list
+    Revoke {
+        #[arg(long, default_value = "revocation_list.json")]
+        list: PathBuf,
+
+        #[arg(long = "certificate-hash", required = true)]
+        certificate_hashes: Vec<String>,
+    },
+    /// Remove certificate hashes from the list
+    Reinstate {
+        #[arg(long, default_value = "revocation_list.json")]
+        list: PathBuf,
+
+        #[arg(long = "certificate-hash", required = true)]
+        certificate_hashes: Vec<String>,
+    },
+    /// Emit a non-membership witness for a certificate hash
+    Witness {
+        #[arg(long, default_value = "revocation_list.json")]
+        list: PathBuf,
+
+        #[arg(long)]
+        certificate_hash: String,
+
+        #[arg(long, default_value = "revocation_witness.json")]
+        output: PathBuf,
+    },
+}
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+fn parse_hashes(hashes: &[String]) -> Vec<[u8; 32]> {
+    hashes
+        .iter()
+        .map(|h| parse_bytes32(h).unwrap_or_else(|| {
+            eprintln!("Error: invalid certificate hash {}", h);
+            std::process::exit(1);
+        }))
+        .collect()
+}
+
+fn load(path: &Path) -> RevocationList {
+    let list = RevocationList::load(path).expect("Failed to load revocation list");
+    list.tree().expect("Invalid revocation list");
+    list
+}
+
+fn save(list: &RevocationList, path: &Path) {
+    println!("=== Revocation Tree ===");
+    println!("Revoked Certificates: {}", list.revoked_count);
+    println!("Depth: {}", list.depth);
+    println!("Revocation Root: {}", list.root);
+
+    list.save(path).expect("Failed to write revocation list");
+    println!("✓ Revocation list saved to: {}", path.display());
+}
+
+fn main() {
+    let args = Args::parse();
+
+    match args.command {
+        Command::Init { list } => {
+            if list.exists() {
+                eprintln!("Error: {} already exists", list.display());
+                std::process::exit(1);
+            }
+            save(&RevocationList::new(&[], now()), &list);
+        }
+        Command::Revoke { list: path, certificate_hashes } => {
+            let updated = load(&path).revoke(&parse_hashes(&certificate_hashes), now()).unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            });
+            save(&updated, &path);
+        }
+        Command::Reinstate { list: path, certificate_hashes } => {
+            let updated = load(&path).reinstate(&parse_hashes(&certificate_hashes), now()).unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            });
+            save(&updated, &path);
+        }
+        Command::Witness { list, certificate_hash, output } => {
+            let list = RevocationList::load(&list).expect("Failed to load revocation list");
+            let tree = list.tree().expect("Invalid revocation list");
+            let certificate_hash = parse_bytes32(&certificate_hash).expect("Invalid certificate hash");
+
+            let proof = match tree.non_membership_proof(&certificate_hash) {
+                Some(proof) => proof,
+                None => {
+                    eprintln!("Error: certificate 0x{} is revoked", hex::encode(certificate_hash));
+                    std::process::exit(1);
+                }
+            };
+
+            let witness_json = serde_json::json!({
+                "certificateHash": format!("0x{}", hex::encode(certificate_hash)),
+                "revocationRoot": list.root,
+                "listUpdatedAt": list.updated_at,
+                "proof": proof,
+            });
+            std::fs::write(&output, serde_json::to_string_pretty(&witness_json).unwrap())
+                .expect("Failed to write witness");
+
+            println!("Certificate Hash: 0x{}", hex::encode(certificate_hash));
+            println!("Neighbours: 0x{} < hash < 0x{}", hex::encode(proof.low), hex::encode(proof.high));
+            println!("✓ Witness saved to: {}", output.display());
+        }
+    }
+}
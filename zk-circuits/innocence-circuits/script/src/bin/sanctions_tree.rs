@@ -0,0 +1,108 @@
+//! Innocence Protocol - Sanctions Tree Builder
+//!
+//! Builds the canonical sanctions tree from OFAC / plain address lists, writes a
+//! versioned snapshot with the `sanctionsRoot` for the contracts, and emits
+//! non-membership witnesses for the innocence proof.
+
+use clap::{Parser, Subcommand};
+use innocence_prover::parse_address;
+use innocence_prover::sanctions::{ListFormat, SanctionsSnapshot};
+use std::path::PathBuf;
+
+/// The arguments for the command
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Build a snapshot from one or more sanctions lists
+    Build {
+        /// Input list files (format detected from extension unless --format is given)
+        #[arg(long = "input", required = true)]
+        inputs: Vec<PathBuf>,
+
+        #[arg(long, value_enum)]
+        format: Option<ListFormat>,
+
+        #[arg(long, default_value = "sanctions_snapshot.json")]
+        output: PathBuf,
+    },
+    /// Emit a non-membership witness for an address from a snapshot
+    Witness {
+        #[arg(long)]
+        snapshot: PathBuf,
+
+        #[arg(long)]
+        address: String,
+
+        #[arg(long, default_value = "sanctions_witness.json")]
+        output: PathBuf,
+    },
+}
+
+fn main() {
+    let args = Args::parse();
+
+    match args.command {
+        Command::Build { inputs, format, output } => {
+            let sources: Vec<_> = inputs
+                .iter()
+                .map(|path| (path.as_path(), format.unwrap_or_else(|| ListFormat::detect(path))))
+                .collect();
+
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+
+            let snapshot = SanctionsSnapshot::from_sources(&sources, timestamp).unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            });
+
+            println!("=== Sanctions Tree ===");
+            for source in &snapshot.sources {
+                println!("Source: {} ({:?}, {} addresses, sha256 {})",
+                    source.path, source.format, source.address_count, source.sha256);
+            }
+            println!("Leaf Count: {}", snapshot.leaf_count);
+            println!("Depth: {}", snapshot.depth);
+            println!("Sanctions Root: {}", snapshot.root);
+            let allowlist = snapshot.allowlist_tree().expect("Invalid snapshot");
+            println!("Allowlist Root: 0x{} (if used as an allowlist)", hex::encode(allowlist.root()));
+
+            snapshot.save(&output).expect("Failed to write snapshot");
+            println!("✓ Snapshot saved to: {}", output.display());
+        }
+        Command::Witness { snapshot, address, output } => {
+            let snapshot = SanctionsSnapshot::load(&snapshot).expect("Failed to load snapshot");
+            let tree = snapshot.tree().expect("Invalid snapshot");
+            let address = parse_address(&address).expect("Invalid address");
+
+            let proof = match tree.non_membership_proof(&address) {
+                Some(proof) => proof,
+                None => {
+                    eprintln!("Error: 0x{} is on the sanctions list", hex::encode(address));
+                    std::process::exit(1);
+                }
+            };
+
+            let witness_json = serde_json::json!({
+                "address": format!("0x{}", hex::encode(address)),
+                "sanctionsRoot": snapshot.root,
+                "snapshotTimestamp": snapshot.timestamp,
+                "proof": proof,
+            });
+            std::fs::write(&output, serde_json::to_string_pretty(&witness_json).unwrap())
+                .expect("Failed to write witness");
+
+            println!("Address: 0x{}", hex::encode(address));
+            println!("Neighbours: 0x{} < address < 0x{}", hex::encode(proof.low), hex::encode(proof.high));
+            println!("✓ Witness saved to: {}", output.display());
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! Innocence Protocol - Price Attestation Signer
+//!
+//! Signs a price attestation as a price oracle for `trade-proof --attestation`.
+//! The price is given in quote-asset units per base-asset unit, e.g.
+//! `--base USDE --quote UETH --price 0.00033`.
+
+use clap::Parser;
+use innocence_prover::assets::load_registry;
+use innocence_prover::oracle::{format_price, parse_price, parse_signing_key, save_attestation, sign_price};
+use std::path::PathBuf;
+
+/// The arguments for the command
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Asset being priced (symbol or id)
+    #[arg(long)]
+    base: String,
+
+    /// Asset the price is quoted in (symbol or id)
+    #[arg(long)]
+    quote: String,
+
+    /// Quote units per base unit, e.g. `0.00033`
+    #[arg(long)]
+    price: String,
+
+    /// Unix time of the price (defaults to now)
+    #[arg(long)]
+    timestamp: Option<u64>,
+
+    /// Oracle private key
+    #[arg(long, env = "ORACLE_PRIVATE_KEY", hide_env_values = true)]
+    key: String,
+
+    /// Asset registry file (defaults to the built-in assets.toml)
+    #[arg(long, env = "ASSET_REGISTRY")]
+    asset_registry: Option<PathBuf>,
+
+    #[arg(long, default_value = "price_attestation.json")]
+    output: PathBuf,
+}
+
+fn main() {
+    dotenv::dotenv().ok();
+    let args = Args::parse();
+
+    let registry = load_registry(args.asset_registry.as_deref()).expect("Failed to load asset registry");
+    let base = registry.resolve(&args.base).expect("Unknown base asset");
+    let quote = registry.resolve(&args.quote).expect("Unknown quote asset");
+    let price = parse_price(base, quote, &args.price).expect("Invalid price");
+    let key = parse_signing_key(&args.key).expect("Invalid oracle key");
+    let timestamp = args.timestamp.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    });
+
+    let signed = sign_price(&key, base.id.0, quote.id.0, price, timestamp);
+    save_attestation(&signed, &args.output).expect("Failed to write attestation");
+
+    println!("Oracle: 0x{}", hex::encode(signed.signer));
+    println!("Price: {} {} per {} (raw {})", format_price(base, quote, price), quote.symbol, base.symbol, price);
+    println!("Timestamp: {}", timestamp);
+    println!("✓ Attestation saved to: {}", args.output.display());
+}
@@ -1,17 +1,31 @@
 //! Innocence Protocol - Trade Proof Generator
 //!
-//! This script generates ZK proofs for private trading.
+//! This script generates ZK proofs for private trading. The guaranteed output is
+//! bounded by a signed oracle price attestation (`--attestation`, from `sign-price`);
+//! without one a test attestation is signed with `--oracle-key`.
 
 use alloy_sol_types::SolType;
 use clap::Parser;
 use hex;
-use innocence_circuits_lib::{TradeProofPublicValues, compute_commitment};
+use innocence_circuits_lib::oracle::{meets_min_output, min_output, PRICE_SCALE};
+use innocence_circuits_lib::{
+    note_public_key, AssetId, IncrementalMerkleTree, Note, SwapNoteProofPublicValues,
+};
+use innocence_prover::assets::{load_registry, parse_amount};
+use innocence_prover::indexer::DepositTree;
+use innocence_prover::notes::{load_note, random_note, save_note};
+use innocence_prover::oracle::{format_price, load_attestation, parse_signing_key, sign_price};
+use innocence_prover::parse_address;
 use sp1_sdk::{include_elf, ProverClient, SP1Stdin, HashableKey};
 use serde_json;
+use std::path::PathBuf;
 
 /// The ELF file for the trade proof circuit
 pub const TRADE_PROOF_ELF: &[u8] = include_elf!("innocence-trade-proof");
 
+/// Depth of the example deposit tree used without --tree
+const TREE_DEPTH: usize = 2;
+
 /// The arguments for the command
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -22,29 +36,71 @@ struct Args {
     #[arg(long)]
     prove: bool,
 
+    /// Spending key of the input note
+    #[arg(long, env = "NOTE_SPENDING_KEY", hide_env_values = true)]
+    spending_key: Option<String>,
+
+    /// Blinding of the example input note
     #[arg(long)]
-    secret: Option<String>,
+    blinding: Option<String>,
 
+    /// Amount blinding of the example input note
     #[arg(long)]
-    nullifier: Option<String>,
-    
+    amount_blinding: Option<String>,
+
+    /// Input note file (overrides --blinding, --amount-blinding, --from-balance and --from-asset)
     #[arg(long)]
-    from_balance: Option<u64>,
+    note: Option<PathBuf>,
     
-    #[arg(long)]
-    to_balance: Option<u64>,
+    /// Amounts are in asset units, e.g. `1.5`
+    #[arg(long, default_value = "10000")]
+    from_balance: String,
     
-    #[arg(long)]
-    from_asset: Option<u64>,
+    /// Asset symbols or ids from the asset registry
+    #[arg(long, default_value = "USDE")]
+    from_asset: String,
     
-    #[arg(long)]
-    to_asset: Option<u64>,
+    #[arg(long, default_value = "UETH")]
+    to_asset: String,
     
-    #[arg(long)]
-    from_amount: Option<u64>,
+    #[arg(long, default_value = "1000")]
+    from_amount: String,
     
+    /// Guaranteed output; defaults to the attested price less --max-slippage-bps
+    #[arg(long)]
+    min_to_amount: Option<String>,
+
+    /// Signed price attestation from `sign-price`
     #[arg(long)]
-    min_to_amount: Option<u64>,
+    attestation: Option<PathBuf>,
+
+    /// Oracle address the attestation must be signed by (defaults to its signer)
+    #[arg(long, env = "PRICE_ORACLE_ADDRESS")]
+    oracle: Option<String>,
+
+    /// Oracle key used to sign a test attestation when no --attestation is given
+    #[arg(long, env = "ORACLE_PRIVATE_KEY", hide_env_values = true)]
+    oracle_key: Option<String>,
+
+    /// Largest allowed shortfall of the output against the attested price
+    #[arg(long, default_value_t = 50)]
+    max_slippage_bps: u32,
+
+    /// Asset registry file (defaults to the built-in assets.toml)
+    #[arg(long, env = "ASSET_REGISTRY")]
+    asset_registry: Option<PathBuf>,
+
+    /// Amounts are integers in base units rather than asset units
+    #[arg(long)]
+    base_units: bool,
+
+    /// Deposit tree state from `tree-indexer` (defaults to an example tree)
+    #[arg(long)]
+    tree: Option<PathBuf>,
+
+    /// Directory the change and output notes are written to
+    #[arg(long, default_value = ".")]
+    notes_dir: PathBuf,
 }
 
 fn main() {
@@ -61,8 +117,8 @@ fn main() {
     }
 
     // Setup test data
-    let secret = if let Some(s) = args.secret {
-        let bytes = hex::decode(s.trim_start_matches("0x")).expect("Invalid secret hex");
+    let spending_key = if let Some(s) = &args.spending_key {
+        let bytes = hex::decode(s.trim_start_matches("0x")).expect("Invalid spending key hex");
         let mut arr = [0u8; 32];
         arr.copy_from_slice(&bytes);
         arr
@@ -70,8 +126,8 @@ fn main() {
         [1u8; 32]
     };
 
-    let nullifier = if let Some(n) = args.nullifier {
-        let bytes = hex::decode(n.trim_start_matches("0x")).expect("Invalid nullifier hex");
+    let blinding = if let Some(b) = &args.blinding {
+        let bytes = hex::decode(b.trim_start_matches("0x")).expect("Invalid blinding hex");
         let mut arr = [0u8; 32];
         arr.copy_from_slice(&bytes);
         arr
@@ -79,16 +135,117 @@ fn main() {
         [2u8; 32]
     };
 
+    let amount_blinding = if let Some(b) = &args.amount_blinding {
+        let bytes = hex::decode(b.trim_start_matches("0x")).expect("Invalid amount blinding hex");
+        let mut arr = [0u8; 32];
+        arr.copy_from_slice(&bytes);
+        arr
+    } else {
+        [3u8; 32]
+    };
+
+    let registry = load_registry(args.asset_registry.as_deref()).expect("Failed to load asset registry");
+
+    // Input note being spent
+    let input_note = match &args.note {
+        Some(path) => load_note(path).expect("Failed to load input note"),
+        None => {
+            let asset = registry.resolve(&args.from_asset).expect("Unknown from asset");
+            Note {
+                asset_id: asset.id.0,
+                amount: parse_amount(asset, &args.from_balance, args.base_units).expect("Invalid from balance"),
+                secret: note_public_key(&spending_key),
+                blinding,
+                amount_blinding,
+            }
+        }
+    };
+
     // Trade parameters
-    let from_balance = args.from_balance.unwrap_or(10_000); // 10k USDC
-    let to_balance = args.to_balance.unwrap_or(0); // 0 ETH
-    let from_asset = args.from_asset.unwrap_or(0); // USDC
-    let to_asset = args.to_asset.unwrap_or(1); // ETH
-    let from_amount = args.from_amount.unwrap_or(1_000); // Trade 1k USDC
-    let min_to_amount = args.min_to_amount.unwrap_or(500); // Expect at least 0.5 ETH
+    let from = registry.check(AssetId(input_note.asset_id)).expect("Input note asset is not registered");
+    let to = registry.resolve(&args.to_asset).expect("Unknown to asset");
+    let from_asset = from.id.0;
+    let to_asset = to.id.0;
+    let from_amount = parse_amount(from, &args.from_amount, args.base_units).expect("Invalid from amount");
+    if from_amount.is_zero() {
+        eprintln!("Error: --from-amount must be greater than zero");
+        std::process::exit(1);
+    }
 
-    // Compute commitment
-    let commitment = compute_commitment(&secret, &nullifier);
+    // Price attestation and the guaranteed output it allows
+    let (signed_price, min_to_amount) = match &args.attestation {
+        Some(path) => {
+            let signed_price = load_attestation(path).expect("Failed to load price attestation");
+            let min_to_amount = match &args.min_to_amount {
+                Some(amount) => parse_amount(to, amount, args.base_units).expect("Invalid min to amount"),
+                None => min_output(from_amount, signed_price.price, args.max_slippage_bps).expect("Invalid slippage"),
+            };
+            (signed_price, min_to_amount)
+        }
+        None => {
+            // Test oracle: attest the price implied by the requested output
+            let key = args
+                .oracle_key
+                .as_deref()
+                .map(|k| parse_signing_key(k).expect("Invalid oracle key"))
+                .expect("--attestation or --oracle-key is required");
+            let amount = args.min_to_amount.as_deref().unwrap_or("1");
+            let min_to_amount = parse_amount(to, amount, args.base_units).expect("Invalid min to amount");
+            let price = min_to_amount
+                .checked_mul(PRICE_SCALE)
+                .and_then(|scaled| scaled.checked_div(from_amount))
+                .expect("Test price overflows");
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+            println!("Warning: no --attestation given, signing a test price with the oracle key");
+            (sign_price(&key, from_asset, to_asset, price, timestamp), min_to_amount)
+        }
+    };
+    let oracle = match &args.oracle {
+        Some(address) => parse_address(address).expect("Invalid oracle address"),
+        None => signed_price.signer,
+    };
+
+    // Fail early rather than in the circuit
+    signed_price.verify(&oracle).expect("Price attestation rejected");
+    assert!(
+        signed_price.base_asset == from_asset && signed_price.quote_asset == to_asset,
+        "Price attestation is for a different asset pair"
+    );
+    assert!(
+        meets_min_output(from_amount, min_to_amount, signed_price.price, args.max_slippage_bps).expect("Invalid slippage"),
+        "Min to amount is below the attested price less slippage"
+    );
+
+    let change_amount = input_note
+        .amount
+        .checked_sub(from_amount)
+        .expect("Insufficient balance in input note");
+
+    // Fresh notes for the change and the swap output, owned by the same key
+    let change_note = random_note(&input_note.secret, from_asset, change_amount);
+    let output_note = random_note(&input_note.secret, to_asset, min_to_amount);
+
+    let commitment = input_note.commitment();
+
+    // Merkle path for the note in the deposit tree
+    let leaf = input_note.leaf();
+    let (merkle_root, merkle_proof) = match &args.tree {
+        Some(path) => {
+            let state = DepositTree::load(path).expect("Failed to load deposit tree");
+            let (_, proof) = state.path(&leaf).expect("Input note is not in the deposit tree");
+            (state.root(), proof)
+        }
+        None => {
+            // Example deposit tree: our note at position 0, every other leaf empty
+            let mut tree = IncrementalMerkleTree::new(TREE_DEPTH).expect("Invalid tree depth");
+            let leaf_index = tree.append(leaf).expect("Deposit tree is full");
+            (tree.root(), tree.proof(leaf_index).expect("Leaf not in tree"))
+        }
+    };
+    let nullifier_hash = input_note.nullifier_hash(&spending_key, merkle_proof.leaf_index);
 
     // Setup the prover client
     // Use local proving (no network access needed)
@@ -96,21 +253,31 @@ fn main() {
 
     // Setup the inputs
     let mut stdin = SP1Stdin::new();
-    stdin.write(&secret);
-    stdin.write(&nullifier);
-    stdin.write(&from_balance);
-    stdin.write(&to_balance);
-    stdin.write(&commitment);
+    stdin.write(&input_note);
+    stdin.write(&spending_key);
+    stdin.write(&merkle_proof);
+    stdin.write(&change_note);
+    stdin.write(&output_note);
+    stdin.write(&signed_price);
+    stdin.write(&merkle_root);
     stdin.write(&from_asset);
     stdin.write(&to_asset);
     stdin.write(&from_amount);
     stdin.write(&min_to_amount);
+    stdin.write(&oracle);
+    stdin.write(&args.max_slippage_bps);
 
     println!("=== Trade Proof Generation ===");
     println!("Commitment: 0x{}", hex::encode(commitment));
-    println!("From Asset: {} Balance: {}", from_asset, from_balance);
-    println!("To Asset: {} Balance: {}", to_asset, to_balance);
-    println!("Trade Amount: {} -> Min: {}", from_amount, min_to_amount);
+    println!("Nullifier Hash: 0x{}", hex::encode(nullifier_hash));
+    println!("Merkle Root: 0x{}", hex::encode(merkle_root));
+    println!("From Asset: {} Balance: {}", from, from.format_amount(input_note.amount));
+    println!("To Asset: {}", to);
+    println!("Trade Amount: {} {} -> Min: {} {}", from.format_amount(from_amount), from.symbol, to.format_amount(min_to_amount), to.symbol);
+    println!("Oracle: 0x{} Price: {} {} per {} at {}", hex::encode(oracle), format_price(from, to, signed_price.price), to.symbol, from.symbol, signed_price.timestamp);
+    println!("Max Slippage: {} bps", args.max_slippage_bps);
+    println!("Change Note: 0x{} ({} {})", hex::encode(change_note.leaf()), from.format_amount(change_amount), from.symbol);
+    println!("Output Note: 0x{} ({} {})", hex::encode(output_note.leaf()), to.format_amount(min_to_amount), to.symbol);
 
     if args.execute {
         // Execute the program
@@ -118,13 +285,20 @@ fn main() {
         println!("\nProgram executed successfully!");
 
         // Read the output
-        let decoded = TradeProofPublicValues::abi_decode(output.as_slice()).unwrap();
+        let decoded = SwapNoteProofPublicValues::abi_decode(output.as_slice()).unwrap();
         println!("\nPublic outputs:");
         println!("  Commitment: 0x{}", hex::encode(decoded.commitment));
-        println!("  From Asset: {}", decoded.fromAsset);
-        println!("  To Asset: {}", decoded.toAsset);
-        println!("  From Amount: {}", decoded.fromAmount);
-        println!("  Min To Amount: {}", decoded.minToAmount);
+        println!("  Nullifier Hash: 0x{}", hex::encode(decoded.nullifierHash));
+        println!("  Token In: {}", decoded.tokenIn);
+        println!("  Token Out: {}", decoded.tokenOut);
+        println!("  Amount In: {}", decoded.amountIn);
+        println!("  Min Amount Out: {}", decoded.minAmountOut);
+        println!("  Merkle Root: 0x{}", hex::encode(decoded.merkleRoot));
+        println!("  Change Leaf: 0x{}", hex::encode(decoded.changeLeaf));
+        println!("  Output Leaf: 0x{}", hex::encode(decoded.outputLeaf));
+        println!("  Oracle: {}", decoded.oracle);
+        println!("  Price Timestamp: {}", decoded.priceTimestamp);
+        println!("  Max Slippage Bps: {}", decoded.maxSlippageBps);
 
         // Record the number of cycles executed
         println!("\nNumber of cycles: {}", report.total_instruction_count());
@@ -172,7 +346,11 @@ fn main() {
             "proof": proof,
             "rawBytes": format!("0x{}", hex::encode(&proof_bytes)),
             "publicValues": format!("0x{}", hex::encode(&proof.public_values.as_slice())),
-            "vkey": format!("0x{}", hex::encode(&vk.bytes32()))
+            "vkey": format!("0x{}", hex::encode(&vk.bytes32())),
+            "nullifierHash": format!("0x{}", hex::encode(nullifier_hash)),
+            "changeLeaf": format!("0x{}", hex::encode(change_note.leaf())),
+            "outputLeaf": format!("0x{}", hex::encode(output_note.leaf())),
+            "priceTimestamp": signed_price.timestamp
         });
 
         // Save the proof to a file
@@ -180,5 +358,12 @@ fn main() {
         std::fs::write(proof_path, serde_json::to_string_pretty(&proof_json).unwrap())
             .expect("Failed to write proof");
         println!("✓ Proof saved to: {}", proof_path);
+
+        // Save the new notes; they are needed to spend the trade proceeds
+        for (name, note) in [("trade_change_note.json", &change_note), ("trade_output_note.json", &output_note)] {
+            let note_path = args.notes_dir.join(name);
+            save_note(note, &note_path).expect("Failed to write note");
+            println!("✓ Note saved to: {}", note_path.display());
+        }
     }
 }
\ No newline at end of file
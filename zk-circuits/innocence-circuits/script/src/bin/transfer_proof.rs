@@ -0,0 +1,247 @@
+//! Innocence Protocol - Transfer Proof Generator
+//!
+//! This script generates ZK proofs for private transfers inside the pool. It spends
+//! one or two of the sender's notes and creates a note for the recipient plus a
+//! change note for the sender.
+
+use alloy_sol_types::SolType;
+use clap::Parser;
+use hex;
+use innocence_circuits_lib::{
+    note_public_key, AssetId, IncrementalMerkleTree, MerkleProof, Note, TransferProofPublicValues, U256,
+};
+use innocence_prover::assets::{load_registry, parse_amount};
+use innocence_prover::indexer::DepositTree;
+use innocence_prover::notes::{load_note, random_note, save_note};
+use innocence_prover::parse_bytes32;
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin, HashableKey};
+use serde_json;
+use std::path::PathBuf;
+
+/// The ELF file for the transfer proof circuit
+pub const TRANSFER_PROOF_ELF: &[u8] = include_elf!("innocence-transfer-proof");
+
+/// Depth of the example deposit tree used without --tree
+const TREE_DEPTH: usize = 2;
+
+/// The arguments for the command
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[arg(long)]
+    execute: bool,
+
+    #[arg(long)]
+    prove: bool,
+
+    /// Note files to spend (one or two, same asset)
+    #[arg(long = "input", required = true, num_args = 1..=2)]
+    inputs: Vec<PathBuf>,
+
+    /// Spending key owning the input notes
+    #[arg(long, env = "NOTE_SPENDING_KEY", hide_env_values = true)]
+    spending_key: String,
+
+    /// Recipient's public note key
+    #[arg(long)]
+    recipient_key: String,
+
+    /// Amount sent to the recipient in asset units, e.g. `1.5`; the rest comes back as change
+    #[arg(long)]
+    amount: String,
+
+    /// Asset registry file (defaults to the built-in assets.toml)
+    #[arg(long, env = "ASSET_REGISTRY")]
+    asset_registry: Option<PathBuf>,
+
+    /// Amounts are integers in base units rather than asset units
+    #[arg(long)]
+    base_units: bool,
+
+    /// Deposit tree state from `tree-indexer` (defaults to an example tree)
+    #[arg(long)]
+    tree: Option<PathBuf>,
+
+    /// Directory the recipient and change notes are written to
+    #[arg(long, default_value = ".")]
+    notes_dir: PathBuf,
+}
+
+fn main() {
+    // Setup the logger
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    // Parse the command line arguments
+    let args = Args::parse();
+
+    if args.execute == args.prove {
+        eprintln!("Error: You must specify either --execute or --prove");
+        std::process::exit(1);
+    }
+
+    let spending_key = parse_bytes32(&args.spending_key).expect("Invalid spending key hex");
+    let recipient_key = parse_bytes32(&args.recipient_key).expect("Invalid recipient key hex");
+    let sender_key = note_public_key(&spending_key);
+    let registry = load_registry(args.asset_registry.as_deref()).expect("Failed to load asset registry");
+
+    // Notes being spent
+    let mut inputs: Vec<Note> = args
+        .inputs
+        .iter()
+        .map(|path| load_note(path).expect("Failed to load input note"))
+        .collect();
+    let asset_id = inputs[0].asset_id;
+    let asset = registry.check(AssetId(asset_id)).expect("Input note asset is not registered");
+    let amount = parse_amount(asset, &args.amount, args.base_units).expect("Invalid amount");
+    for note in &inputs {
+        assert_eq!(note.asset_id, asset_id, "Input notes must hold the same asset");
+        assert!(note.is_owned_by(&spending_key), "Input note is not owned by the spending key");
+    }
+    let total = inputs
+        .iter()
+        .try_fold(U256::ZERO, |total, note| total.checked_add(note.amount))
+        .expect("Input amount overflow");
+    let change_amount = total.checked_sub(amount).expect("Insufficient balance in input notes");
+
+    // Merkle paths for the real inputs
+    let leaves: Vec<[u8; 32]> = inputs.iter().map(Note::leaf).collect();
+    let (merkle_root, mut merkle_proofs) = match &args.tree {
+        Some(path) => {
+            let state = DepositTree::load(path).expect("Failed to load deposit tree");
+            let proofs: Vec<MerkleProof> = leaves
+                .iter()
+                .map(|leaf| state.path(leaf).expect("Input note is not in the deposit tree").1)
+                .collect();
+            (state.root(), proofs)
+        }
+        None => {
+            // Example deposit tree holding just the input notes
+            let tree = IncrementalMerkleTree::from_leaves(TREE_DEPTH, &leaves).expect("Invalid example tree");
+            let proofs: Vec<MerkleProof> = (0..leaves.len() as u64)
+                .map(|i| tree.proof(i).expect("Leaf not in tree"))
+                .collect();
+            (tree.root(), proofs)
+        }
+    };
+    let mut spending_keys = vec![spending_key; inputs.len()];
+
+    // Pad the second slot with a zero-amount dummy note; it needs no path or key
+    if inputs.len() == 1 {
+        let dummy = random_note(&[0u8; 32], asset_id, U256::ZERO);
+        merkle_proofs.push(MerkleProof { leaf: dummy.leaf(), path: vec![], leaf_index: 0 });
+        spending_keys.push([0u8; 32]);
+        inputs.push(dummy);
+    }
+
+    // Fresh notes for the recipient and the change
+    let recipient_note = random_note(&recipient_key, asset_id, amount);
+    let change_note = random_note(&sender_key, asset_id, change_amount);
+
+    // Setup the prover client
+    // Use local proving (no network access needed)
+    let client = ProverClient::from_env();
+
+    // Setup the inputs
+    let mut stdin = SP1Stdin::new();
+    for i in 0..2 {
+        stdin.write(&inputs[i]);
+        stdin.write(&merkle_proofs[i]);
+        stdin.write(&spending_keys[i]);
+    }
+    stdin.write(&recipient_note);
+    stdin.write(&change_note);
+    stdin.write(&merkle_root);
+
+    println!("=== Transfer Proof Generation ===");
+    println!("Sender Note Key: 0x{}", hex::encode(sender_key));
+    println!("Merkle Root: 0x{}", hex::encode(merkle_root));
+    for (i, note) in inputs.iter().enumerate() {
+        println!("Input {}: 0x{} ({} {})", i + 1, hex::encode(note.leaf()), asset.format_amount(note.amount), asset.symbol);
+    }
+    println!("Recipient Note: 0x{} ({} {})", hex::encode(recipient_note.leaf()), asset.format_amount(amount), asset.symbol);
+    println!("Change Note: 0x{} ({} {})", hex::encode(change_note.leaf()), asset.format_amount(change_amount), asset.symbol);
+
+    if args.execute {
+        // Execute the program
+        let (output, report) = client.execute(TRANSFER_PROOF_ELF, &stdin).run().unwrap();
+        println!("\nProgram executed successfully!");
+
+        // Read the output
+        let decoded = TransferProofPublicValues::abi_decode(output.as_slice()).unwrap();
+        println!("\nPublic outputs:");
+        println!("  Merkle Root: 0x{}", hex::encode(decoded.merkleRoot));
+        println!("  Input Nullifier Hash 1: 0x{}", hex::encode(decoded.inputNullifierHash1));
+        println!("  Input Nullifier Hash 2: 0x{}", hex::encode(decoded.inputNullifierHash2));
+        println!("  Output Leaf 1: 0x{}", hex::encode(decoded.outputLeaf1));
+        println!("  Output Leaf 2: 0x{}", hex::encode(decoded.outputLeaf2));
+
+        // Record the number of cycles executed
+        println!("\nNumber of cycles: {}", report.total_instruction_count());
+    } else {
+        // Setup the program for proving
+        println!("\nSetting up proving keys...");
+        let (pk, vk) = client.setup(TRANSFER_PROOF_ELF);
+
+        // Generate the proof
+        let use_groth16 = std::env::var("USE_GROTH16").unwrap_or_else(|_| "false".to_string()) == "true";
+
+        let proof = if use_groth16 {
+            println!("Generating Groth16 proof...");
+            client
+                .prove(&pk, &stdin)
+                .groth16()
+                .run()
+                .expect("failed to generate proof")
+        } else {
+            println!("Generating Core proof...");
+            client
+                .prove(&pk, &stdin)
+                .run()
+                .expect("failed to generate proof")
+        };
+
+        println!("✓ Successfully generated {} proof!", if use_groth16 { "Groth16" } else { "Core" });
+
+        // Verify the proof
+        client.verify(&proof, &vk).expect("failed to verify proof");
+        println!("✓ Successfully verified proof!");
+
+        // Get the raw proof bytes for the verifier (if supported)
+        let proof_bytes = if use_groth16 {
+            proof.bytes()
+        } else {
+            // For Core proofs, we'll use a placeholder
+            // In production, you must use Groth16 or PLONK for on-chain verification
+            vec![0u8; 32]
+        };
+        println!("Proof bytes (for verifier): 0x{}", hex::encode(&proof_bytes));
+
+        // Create proof JSON with both SP1 proof format and raw bytes
+        let proof_json = serde_json::json!({
+            "proof": proof,
+            "rawBytes": format!("0x{}", hex::encode(&proof_bytes)),
+            "publicValues": format!("0x{}", hex::encode(&proof.public_values.as_slice())),
+            "vkey": format!("0x{}", hex::encode(&vk.bytes32())),
+            "inputNullifierHashes": [
+                format!("0x{}", hex::encode(inputs[0].nullifier_hash(&spending_keys[0], merkle_proofs[0].leaf_index))),
+                format!("0x{}", hex::encode(inputs[1].nullifier_hash(&spending_keys[1], merkle_proofs[1].leaf_index)))
+            ],
+            "recipientLeaf": format!("0x{}", hex::encode(recipient_note.leaf())),
+            "changeLeaf": format!("0x{}", hex::encode(change_note.leaf()))
+        });
+
+        // Save the proof to a file
+        let proof_path = "transfer_proof.json";
+        std::fs::write(proof_path, serde_json::to_string_pretty(&proof_json).unwrap())
+            .expect("Failed to write proof");
+        println!("✓ Proof saved to: {}", proof_path);
+
+        // Save the new notes; the recipient note must be handed to the recipient
+        for (name, note) in [("transfer_recipient_note.json", &recipient_note), ("transfer_change_note.json", &change_note)] {
+            let note_path = args.notes_dir.join(name);
+            save_note(note, &note_path).expect("Failed to write note");
+            println!("✓ Note saved to: {}", note_path.display());
+        }
+    }
+}
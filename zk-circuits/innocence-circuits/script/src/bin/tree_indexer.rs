@@ -0,0 +1,225 @@
+//! Innocence Protocol - Deposit Tree Indexer
+//!
+//! Rebuilds the deposit tree from `PrivateDeposit` logs, either live from a
+//! JSON-RPC endpoint or offline from a JSON log dump, checks it against the
+//! contract's `getMerkleRoot()` (which must be the tree root), and serves Merkle paths by deposit commitment.
+
+use clap::{Parser, Subcommand};
+use innocence_circuits_lib::tree::DEFAULT_TREE_DEPTH;
+use innocence_prover::indexer::{parse_deposit_logs, DepositTree, RpcClient};
+use innocence_prover::parse_bytes32;
+use std::path::{Path, PathBuf};
+
+/// The arguments for the command
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Tree state file, created on first use
+    #[arg(long, global = true, default_value = "deposit_tree.json")]
+    state: PathBuf,
+
+    /// Tree depth for a new state file
+    #[arg(long, global = true, default_value_t = DEFAULT_TREE_DEPTH)]
+    depth: usize,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Fetch new deposits from a JSON-RPC endpoint and check the on-chain root
+    Sync {
+        #[arg(long, env = "RPC_URL")]
+        rpc_url: String,
+
+        /// Privacy system contract emitting `PrivateDeposit`
+        #[arg(long, env = "PRIVACY_SYSTEM_ADDRESS")]
+        contract: String,
+
+        /// First block to scan when starting from an empty state
+        #[arg(long, default_value_t = 0)]
+        from_block: u64,
+
+        /// Block range per eth_getLogs request
+        #[arg(long, default_value_t = 2000)]
+        batch_size: u64,
+
+        /// Blocks to stay behind the head to avoid indexing reorged deposits
+        #[arg(long, default_value_t = 0)]
+        confirmations: u64,
+    },
+    /// Import deposits from eth_getLogs JSON dumps
+    Import {
+        #[arg(long = "logs", required = true)]
+        logs: Vec<PathBuf>,
+
+        /// Expected getMerkleRoot() value after the import
+        #[arg(long)]
+        expected_root: Option<String>,
+    },
+    /// Check the local tree against getMerkleRoot() on chain
+    Verify {
+        #[arg(long, env = "RPC_URL")]
+        rpc_url: String,
+
+        #[arg(long, env = "PRIVACY_SYSTEM_ADDRESS")]
+        contract: String,
+    },
+    /// Emit the Merkle path for a deposit commitment
+    Path {
+        #[arg(long)]
+        commitment: String,
+
+        /// Write the path to a file instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+}
+
+fn fail(message: String) -> ! {
+    eprintln!("Error: {}", message);
+    std::process::exit(1);
+}
+
+fn load_or_create(path: &Path, depth: usize, contract: Option<&str>) -> DepositTree {
+    let state = if path.exists() {
+        DepositTree::load(path).unwrap_or_else(|e| fail(e))
+    } else {
+        DepositTree::new(depth, contract.map(str::to_string)).unwrap_or_else(|e| fail(e))
+    };
+
+    if let (Some(recorded), Some(contract)) = (&state.contract, contract) {
+        if !recorded.eq_ignore_ascii_case(contract) {
+            fail(format!("{} indexes {}, not {}", path.display(), recorded, contract));
+        }
+    }
+    state
+}
+
+fn report_root(state: &DepositTree, onchain: &[u8; 32]) {
+    match state.check_root(onchain) {
+        Ok(()) => println!("✓ On-chain root matches the tree root"),
+        Err(e) => fail(e),
+    }
+}
+
+fn main() {
+    dotenv::dotenv().ok();
+    let args = Args::parse();
+
+    match args.command {
+        Command::Sync { rpc_url, contract, from_block, batch_size, confirmations } => {
+            let mut state = load_or_create(&args.state, args.depth, Some(&contract));
+            let client = RpcClient::new(&rpc_url);
+
+            let head = client
+                .block_number()
+                .unwrap_or_else(|e| fail(e))
+                .saturating_sub(confirmations);
+            let start = state.synced_block.map(|b| b + 1).unwrap_or(from_block);
+
+            println!("=== Deposit Tree Sync ===");
+            println!("Contract: {}", contract);
+            println!("Blocks: {} to {}", start, head);
+
+            let mut from = start;
+            while from <= head {
+                let to = head.min(from + batch_size.max(1) - 1);
+                let logs = client.deposit_logs(&contract, from, to).unwrap_or_else(|e| fail(e));
+                let events = parse_deposit_logs(&logs).unwrap_or_else(|e| fail(e));
+                let added = state.apply(&events).unwrap_or_else(|e| fail(e));
+                if added > 0 {
+                    println!("  Blocks {}-{}: {} deposits", from, to, added);
+                }
+                state.synced_block = Some(to);
+                from = to + 1;
+            }
+
+            state.save(&args.state).unwrap_or_else(|e| fail(e));
+            println!("Deposits: {}", state.deposits.len());
+            println!("Tree Root: 0x{}", hex::encode(state.root()));
+            println!("✓ State saved to: {}", args.state.display());
+
+            let onchain = client.merkle_root(&contract, head).unwrap_or_else(|e| fail(e));
+            report_root(&state, &onchain);
+        }
+        Command::Import { logs, expected_root } => {
+            let mut state = load_or_create(&args.state, args.depth, None);
+
+            let mut events = Vec::new();
+            for path in &logs {
+                let data = std::fs::read_to_string(path)
+                    .unwrap_or_else(|e| fail(format!("Failed to read {}: {}", path.display(), e)));
+                let value = serde_json::from_str(&data)
+                    .unwrap_or_else(|e| fail(format!("Invalid JSON in {}: {}", path.display(), e)));
+                events.extend(parse_deposit_logs(&value).unwrap_or_else(|e| fail(format!("{}: {}", path.display(), e))));
+            }
+            events.sort_by_key(|e| e.cursor());
+
+            let added = state.apply(&events).unwrap_or_else(|e| fail(e));
+            if let Some(last) = state.deposits.last() {
+                state.synced_block = state.synced_block.max(Some(last.block_number));
+            }
+
+            println!("=== Deposit Tree Import ===");
+            println!("New Deposits: {}", added);
+            println!("Deposits: {}", state.deposits.len());
+            println!("Tree Root: 0x{}", hex::encode(state.root()));
+
+            if let Some(expected) = expected_root {
+                let expected = parse_bytes32(&expected).unwrap_or_else(|| fail("Invalid expected root".to_string()));
+                report_root(&state, &expected);
+            }
+
+            state.save(&args.state).unwrap_or_else(|e| fail(e));
+            println!("✓ State saved to: {}", args.state.display());
+        }
+        Command::Verify { rpc_url, contract } => {
+            let state = DepositTree::load(&args.state).unwrap_or_else(|e| fail(e));
+            let block = state
+                .synced_block
+                .unwrap_or_else(|| fail("State has not been synced".to_string()));
+            let onchain = RpcClient::new(&rpc_url)
+                .merkle_root(&contract, block)
+                .unwrap_or_else(|e| fail(e));
+
+            println!("Block: {}", block);
+            println!("On-chain Root: 0x{}", hex::encode(onchain));
+            report_root(&state, &onchain);
+        }
+        Command::Path { commitment, output } => {
+            let state = DepositTree::load(&args.state).unwrap_or_else(|e| fail(e));
+            let commitment = parse_bytes32(&commitment).unwrap_or_else(|| fail("Invalid commitment".to_string()));
+            let commitment = format!("0x{}", hex::encode(commitment));
+            let deposit = state
+                .deposit(&commitment)
+                .unwrap_or_else(|| fail(format!("Commitment {} is not in the tree", commitment)));
+            let leaf = deposit.leaf().unwrap_or_else(|e| fail(e));
+            let (index, proof) = state
+                .path(&leaf)
+                .unwrap_or_else(|| fail(format!("Leaf for commitment {} is not in the tree", commitment)));
+
+            let path_json = serde_json::json!({
+                "commitment": commitment,
+                "assetId": deposit.asset_id,
+                "amount": deposit.amount.to_string(),
+                "amountBlinding": deposit.amount_blinding,
+                "leaf": format!("0x{}", hex::encode(leaf)),
+                "leafIndex": index,
+                "merkleRoot": format!("0x{}", hex::encode(state.root())),
+                "depth": state.tree.depth(),
+                "proof": proof,
+            });
+            let json = serde_json::to_string_pretty(&path_json).unwrap();
+
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, json).unwrap_or_else(|e| fail(format!("Failed to write {}: {}", path.display(), e)));
+                    println!("✓ Path for leaf {} saved to: {}", index, path.display());
+                }
+                None => println!("{}", json),
+            }
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! Innocence Protocol - Withdraw Proof Generator
+//!
+//! This script generates ZK proofs for withdrawing a deposit note to a recipient.
+
+use alloy_sol_types::SolType;
+use clap::Parser;
+use innocence_circuits_lib::{
+    note_public_key, AssetId, IncrementalMerkleTree, Note, WithdrawProofPublicValues,
+};
+use innocence_prover::assets::{load_registry, parse_amount};
+use innocence_prover::indexer::DepositTree;
+use innocence_prover::notes::load_note;
+use innocence_prover::{parse_address, parse_bytes32};
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin, HashableKey};
+use std::path::PathBuf;
+
+/// The ELF file for the withdraw proof circuit
+pub const WITHDRAW_PROOF_ELF: &[u8] = include_elf!("innocence-withdraw-proof");
+
+/// Depth of the example deposit tree used without --tree
+const TREE_DEPTH: usize = 2;
+
+/// The arguments for the command
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[arg(long)]
+    execute: bool,
+
+    #[arg(long)]
+    prove: bool,
+
+    /// Spending key of the note
+    #[arg(long, env = "NOTE_SPENDING_KEY", hide_env_values = true)]
+    spending_key: Option<String>,
+
+    /// Blinding of the example note
+    #[arg(long)]
+    blinding: Option<String>,
+
+    /// Amount blinding of the example note
+    #[arg(long)]
+    amount_blinding: Option<String>,
+
+    /// Note file (overrides --blinding, --amount-blinding, --amount and --asset)
+    #[arg(long)]
+    note: Option<PathBuf>,
+
+    /// Amount in asset units, e.g. `1.5`
+    #[arg(long, default_value = "1000")]
+    amount: String,
+
+    /// Asset symbol or id from the asset registry
+    #[arg(long, alias = "asset-id", default_value = "HYPE")]
+    asset: String,
+
+    /// Address receiving the withdrawn funds
+    #[arg(long)]
+    recipient: String,
+
+    /// Relayer submitting the transaction (defaults to none)
+    #[arg(long)]
+    relayer: Option<String>,
+
+    /// Fee paid to the relayer out of the amount
+    #[arg(long, default_value = "0")]
+    fee: String,
+
+    /// Asset registry file (defaults to the built-in assets.toml)
+    #[arg(long, env = "ASSET_REGISTRY")]
+    asset_registry: Option<PathBuf>,
+
+    /// Amounts are integers in base units rather than asset units
+    #[arg(long)]
+    base_units: bool,
+
+    /// Deposit tree state from `tree-indexer` (defaults to an example tree)
+    #[arg(long)]
+    tree: Option<PathBuf>,
+}
+
+fn main() {
+    // Setup the logger
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    // Parse the command line arguments
+    let args = Args::parse();
+
+    if args.execute == args.prove {
+        eprintln!("Error: You must specify either --execute or --prove");
+        std::process::exit(1);
+    }
+
+    // Setup test data
+    let spending_key = args
+        .spending_key
+        .as_deref()
+        .map(|s| parse_bytes32(s).expect("Invalid spending key hex"))
+        .unwrap_or([1u8; 32]);
+    let blinding = args
+        .blinding
+        .as_deref()
+        .map(|b| parse_bytes32(b).expect("Invalid blinding hex"))
+        .unwrap_or([2u8; 32]);
+    let amount_blinding = args
+        .amount_blinding
+        .as_deref()
+        .map(|b| parse_bytes32(b).expect("Invalid amount blinding hex"))
+        .unwrap_or([3u8; 32]);
+
+    let registry = load_registry(args.asset_registry.as_deref()).expect("Failed to load asset registry");
+
+    // Note being withdrawn in full
+    let note = match &args.note {
+        Some(path) => load_note(path).expect("Failed to load note"),
+        None => {
+            let asset = registry.resolve(&args.asset).expect("Unknown asset");
+            Note {
+                asset_id: asset.id.0,
+                amount: parse_amount(asset, &args.amount, args.base_units).expect("Invalid amount"),
+                secret: note_public_key(&spending_key),
+                blinding,
+                amount_blinding,
+            }
+        }
+    };
+    let asset = registry.check(AssetId(note.asset_id)).expect("Note asset is not registered");
+    let asset_id = asset.id.0;
+    let amount = note.amount;
+    let recipient = parse_address(&args.recipient).expect("Invalid recipient address");
+    let relayer = args
+        .relayer
+        .map(|r| parse_address(&r).expect("Invalid relayer address"))
+        .unwrap_or([0u8; 20]);
+    let fee = parse_amount(asset, &args.fee, args.base_units).expect("Invalid fee");
+
+    let commitment = note.commitment();
+
+    // Merkle path for the note in the deposit tree
+    let leaf = note.leaf();
+    let (merkle_root, merkle_proof) = match &args.tree {
+        Some(path) => {
+            let state = DepositTree::load(path).expect("Failed to load deposit tree");
+            let (_, proof) = state.path(&leaf).expect("Note is not in the deposit tree");
+            (state.root(), proof)
+        }
+        None => {
+            // Example deposit tree: our leaf at position 0, every other leaf empty
+            let mut tree = IncrementalMerkleTree::new(TREE_DEPTH).expect("Invalid tree depth");
+            let leaf_index = tree.append(leaf).expect("Deposit tree is full");
+            (tree.root(), tree.proof(leaf_index).expect("Leaf not in tree"))
+        }
+    };
+    let nullifier_hash = note.nullifier_hash(&spending_key, merkle_proof.leaf_index);
+
+    // Setup the prover client
+    let client = ProverClient::from_env();
+
+    // Setup the inputs
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&spending_key);
+    stdin.write(&note);
+    stdin.write(&merkle_proof);
+    stdin.write(&merkle_root);
+    stdin.write(&recipient);
+    stdin.write(&relayer);
+    stdin.write(&fee);
+    stdin.write(&amount);
+    stdin.write(&asset_id);
+
+    println!("=== Withdraw Proof Generation ===");
+    println!("Commitment: 0x{}", hex::encode(commitment));
+    println!("Nullifier Hash: 0x{}", hex::encode(nullifier_hash));
+    println!("Merkle Root: 0x{}", hex::encode(merkle_root));
+    println!("Recipient: 0x{}", hex::encode(recipient));
+    println!("Relayer: 0x{} Fee: {}", hex::encode(relayer), fee);
+    println!("Asset: {} Amount: {} ({} base units)", asset, asset.format_amount(amount), amount);
+
+    if args.execute {
+        // Execute the program
+        let (output, report) = client.execute(WITHDRAW_PROOF_ELF, &stdin).run().unwrap();
+        println!("\nProgram executed successfully!");
+
+        // Read the output
+        let decoded = WithdrawProofPublicValues::abi_decode(output.as_slice()).unwrap();
+        println!("\nPublic outputs:");
+        println!("  Merkle Root: 0x{}", hex::encode(decoded.merkleRoot));
+        println!("  Nullifier Hash: 0x{}", hex::encode(decoded.nullifierHash));
+        println!("  Recipient: 0x{}", hex::encode(decoded.recipient));
+        println!("  Relayer: 0x{}", hex::encode(decoded.relayer));
+        println!("  Fee: {}", decoded.fee);
+        println!("  Amount: {}", decoded.amount);
+        println!("  Asset ID: {}", decoded.assetId);
+
+        // Record the number of cycles executed
+        println!("\nNumber of cycles: {}", report.total_instruction_count());
+    } else {
+        // Setup the program for proving
+        println!("\nSetting up proving keys...");
+        let (pk, vk) = client.setup(WITHDRAW_PROOF_ELF);
+
+        // Generate the proof
+        let use_groth16 = std::env::var("USE_GROTH16").unwrap_or_else(|_| "false".to_string()) == "true";
+
+        let proof = if use_groth16 {
+            println!("Generating Groth16 proof...");
+            client
+                .prove(&pk, &stdin)
+                .groth16()
+                .run()
+                .expect("failed to generate proof")
+        } else {
+            println!("Generating Core proof...");
+            client
+                .prove(&pk, &stdin)
+                .run()
+                .expect("failed to generate proof")
+        };
+
+        println!("✓ Successfully generated {} proof!", if use_groth16 { "Groth16" } else { "Core" });
+
+        // Verify the proof
+        client.verify(&proof, &vk).expect("failed to verify proof");
+        println!("✓ Successfully verified proof!");
+
+        // Get the raw proof bytes for the verifier (if supported)
+        let proof_bytes = if use_groth16 {
+            proof.bytes()
+        } else {
+            // For Core proofs, we'll use a placeholder
+            // In production, you must use Groth16 or PLONK for on-chain verification
+            vec![0u8; 32]
+        };
+        println!("Proof bytes (for verifier): 0x{}", hex::encode(&proof_bytes));
+
+        // Create proof JSON with both SP1 proof format and raw bytes
+        let proof_json = serde_json::json!({
+            "proof": proof,
+            "rawBytes": format!("0x{}", hex::encode(&proof_bytes)),
+            "publicValues": format!("0x{}", hex::encode(proof.public_values.as_slice())),
+            "vkey": vk.bytes32(),
+            "nullifierHash": format!("0x{}", hex::encode(nullifier_hash))
+        });
+
+        // Save the proof to a file
+        let proof_path = "withdraw_proof.json";
+        std::fs::write(proof_path, serde_json::to_string_pretty(&proof_json).unwrap())
+            .expect("Failed to write proof");
+        println!("✓ Proof saved to: {}", proof_path);
+    }
+}
@@ -0,0 +1,169 @@
+//! Compliance certificate and policy files
+//!
+//! A certificate file holds a certificate in its canonical encoding with the
+//! issuer's signature, as written by `issue-certificate`:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "certificate": "0x...",
+//!   "certificateHash": "0x...",
+//!   "issuer": "0x...",
+//!   "signature": "0x..."
+//! }
+//! ```
+//!
+//! `certificateHash` and `issuer` are for reading; loading checks them against
+//! the encoding. The signature is 65 bytes, r || s || v.
+//!
+//! A policy file lists the predicates a venue requires, all of which must hold:
+//!
+//! ```json
+//! {
+//!   "predicates": [
+//!     { "jurisdictionNotIn": ["US", "KP"] },
+//!     { "minKycTier": "standard" },
+//!     { "minAccreditation": "accredited" },
+//!     { "minAge": 18 }
+//!   ]
+//! }
+//! ```
+
+use innocence_circuits_lib::signature::public_key_to_address;
+use innocence_circuits_lib::{CertificateAttributes, ComplianceCertificate, Policy};
+use k256::ecdsa::SigningKey;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Certificate with the issuer's (r, s, v) signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedCertificate {
+    pub certificate: ComplianceCertificate,
+    pub v: u8,
+    pub r: [u8; 32],
+    pub s: [u8; 32],
+}
+
+impl SignedCertificate {
+    /// Check the signature is by the certificate's issuer
+    pub fn verify(&self) -> Result<(), String> {
+        self.certificate
+            .verify_signature(self.v, &self.r, &self.s)
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Certificate as stored on disk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateFile {
+    pub version: u16,
+    /// Canonical encoding, the compliance circuit's input
+    pub certificate: String,
+    pub certificate_hash: String,
+    pub issuer: String,
+    /// 65-byte r || s || v signature
+    pub signature: String,
+}
+
+impl From<&SignedCertificate> for CertificateFile {
+    fn from(signed: &SignedCertificate) -> Self {
+        let mut signature = Vec::with_capacity(65);
+        signature.extend_from_slice(&signed.r);
+        signature.extend_from_slice(&signed.s);
+        signature.push(signed.v);
+        Self {
+            version: signed.certificate.version,
+            certificate: format!("0x{}", hex::encode(signed.certificate.encode())),
+            certificate_hash: format!("0x{}", hex::encode(signed.certificate.hash())),
+            issuer: format!("0x{}", hex::encode(signed.certificate.issuer)),
+            signature: format!("0x{}", hex::encode(signature)),
+        }
+    }
+}
+
+impl TryFrom<&CertificateFile> for SignedCertificate {
+    type Error = String;
+
+    fn try_from(file: &CertificateFile) -> Result<Self, Self::Error> {
+        let data = hex::decode(file.certificate.trim_start_matches("0x"))
+            .map_err(|e| format!("Invalid certificate hex: {}", e))?;
+        let certificate = ComplianceCertificate::decode(&data).map_err(|e| format!("Invalid certificate: {}", e))?;
+        if certificate.version != file.version {
+            return Err(format!("Version {} does not match the certificate's {}", file.version, certificate.version));
+        }
+        if crate::parse_bytes32(&file.certificate_hash) != Some(certificate.hash()) {
+            return Err(format!("Certificate hash {} does not match the certificate", file.certificate_hash));
+        }
+        if crate::parse_address(&file.issuer) != Some(certificate.issuer) {
+            return Err(format!("Issuer {} does not match the certificate", file.issuer));
+        }
+
+        let signature = hex::decode(file.signature.trim_start_matches("0x"))
+            .map_err(|e| format!("Invalid signature hex: {}", e))?;
+        if signature.len() != 65 {
+            return Err(format!("Signature must be 65 bytes, got {}", signature.len()));
+        }
+        Ok(SignedCertificate {
+            certificate,
+            r: signature[..32].try_into().unwrap(),
+            s: signature[32..64].try_into().unwrap(),
+            v: signature[64],
+        })
+    }
+}
+
+/// Load a certificate file; the signature is not checked
+pub fn load_certificate(path: &Path) -> Result<SignedCertificate, String> {
+    let data = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let file: CertificateFile = serde_json::from_str(&data)
+        .map_err(|e| format!("Invalid certificate file {}: {}", path.display(), e))?;
+    SignedCertificate::try_from(&file).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub fn save_certificate(signed: &SignedCertificate, path: &Path) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(&CertificateFile::from(signed)).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Authority signing key from a file holding the key in hex
+pub fn load_authority_key(path: &Path) -> Result<SigningKey, String> {
+    let data = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    crate::oracle::parse_signing_key(data.trim()).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Issue a certificate for `subject` as the authority holding `key`
+pub fn issue_certificate(
+    key: &SigningKey,
+    subject: [u8; 32],
+    issued_at: u64,
+    expires_at: u64,
+    attributes: CertificateAttributes,
+) -> Result<SignedCertificate, String> {
+    let issuer = public_key_to_address(key.verifying_key());
+    let certificate = ComplianceCertificate::new(issuer, subject, issued_at, expires_at, attributes)
+        .map_err(|e| format!("Invalid certificate: {}", e))?;
+
+    let (signature, recovery_id) = key
+        .sign_prehash_recoverable(&certificate.signing_digest())
+        .map_err(|e| format!("Failed to sign certificate: {}", e))?;
+    let (r, s) = signature.split_bytes();
+    Ok(SignedCertificate {
+        certificate,
+        v: 27 + recovery_id.to_byte(),
+        r: r.into(),
+        s: s.into(),
+    })
+}
+
+pub fn load_policy(path: &Path) -> Result<Policy, String> {
+    let data = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let policy: Policy = serde_json::from_str(&data)
+        .map_err(|e| format!("Invalid policy {}: {}", path.display(), e))?;
+    policy.validate().map_err(|e| format!("Invalid policy {}: {}", path.display(), e))?;
+    Ok(policy)
+}
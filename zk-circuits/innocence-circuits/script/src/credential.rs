@@ -0,0 +1,57 @@
+//! Verifiable credential files
+//!
+//! In credential mode `compliance-proof` proves from a compact JWS (ES256K)
+//! issued by a KYC provider, a plain JWT or a W3C VC-JWT (see lib `credential`),
+//! checked against the provider's public JWK:
+//!
+//! ```json
+//! { "kty": "EC", "crv": "secp256k1", "x": "...", "y": "..." }
+//! ```
+//!
+//! Credentials are validated on the host before proving, so a bad token fails
+//! with a clear error rather than a guest panic.
+
+use innocence_circuits_lib::credential::issuer_key_from_jwk;
+use innocence_circuits_lib::Credential;
+use std::fs;
+use std::path::Path;
+
+/// Compact JWS from a file, surrounding whitespace removed
+pub fn load_credential(path: &Path) -> Result<String, String> {
+    let data = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(data.trim().to_string())
+}
+
+/// SEC1 issuer public key from a JWK file
+pub fn load_issuer_jwk(path: &Path) -> Result<Vec<u8>, String> {
+    let data = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    issuer_key_from_jwk(&data).map_err(|e| format!("Invalid JWK {}: {}", path.display(), e))
+}
+
+/// Run the circuit's credential checks natively: the signature is by
+/// `issuer_key`, `sub` is `commitment` and `timestamp` is within nbf and exp
+pub fn validate_credential(
+    token: &str,
+    issuer_key: &[u8],
+    commitment: &[u8; 32],
+    timestamp: u64,
+) -> Result<Credential, String> {
+    let credential = Credential::parse(token).map_err(|e| format!("Invalid credential: {}", e))?;
+    credential.verify(issuer_key).map_err(|e| format!("Invalid credential: {}", e))?;
+
+    let claims = &credential.claims;
+    if claims.subject != *commitment {
+        return Err(format!("Credential subject 0x{} is not the commitment", hex::encode(claims.subject)));
+    }
+    if !claims.is_valid_at(timestamp) {
+        return Err(format!(
+            "Credential is not valid at {} (nbf {}, exp {})",
+            timestamp,
+            claims.not_before.map_or("none".to_string(), |nbf| nbf.to_string()),
+            claims.expires_at
+        ));
+    }
+    Ok(credential)
+}
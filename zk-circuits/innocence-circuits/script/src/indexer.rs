@@ -0,0 +1,332 @@
+//! Deposit tree indexer
+//!
+//! Rebuilds the deposit tree from the privacy system's `PrivateDeposit` logs, so
+//! the prover can produce Merkle paths for real deposits instead of a toy tree.
+//! The tree holds the note leaf of each deposit, `compute_note_leaf(commitment,
+//! token, amount, amount_blinding)`, which the contracts insert and every spending
+//! circuit checks.
+//! Logs come either from a JSON-RPC endpoint (`eth_getLogs`) or from a JSON dump
+//! in the same shape, which is what the recorded test fixtures use.
+
+use innocence_circuits_lib::signature::keccak256;
+use innocence_circuits_lib::{compute_note_leaf, IncrementalMerkleTree, MerkleProof, U256};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+
+/// Indexer state file format version
+pub const STATE_VERSION: u32 = 3;
+
+/// `PrivateDeposit` signatures emitted by the contracts. The commitment is the first
+/// indexed argument in both, so it is always `topics[1]`, and the data always starts
+/// with the deposited `uint64 token`, `uint256 amount` and `bytes32 amountBlinding`.
+pub const PRIVATE_DEPOSIT_EVENTS: &[&str] = &[
+    "PrivateDeposit(bytes32,uint64,uint256,bytes32,uint256)",
+    "PrivateDeposit(bytes32,address,uint64,uint256,bytes32,uint256)",
+];
+
+/// Event selector (topic 0) for an event signature
+pub fn event_topic(signature: &str) -> [u8; 32] {
+    keccak256(signature.as_bytes())
+}
+
+/// A `PrivateDeposit` log, reduced to what the tree needs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositEvent {
+    pub commitment: String,
+    pub asset_id: u64,
+    pub amount: U256,
+    pub amount_blinding: String,
+    pub block_number: u64,
+    pub log_index: u64,
+    pub transaction_hash: Option<String>,
+}
+
+impl DepositEvent {
+    /// Position of the log in chain order
+    pub fn cursor(&self) -> (u64, u64) {
+        (self.block_number, self.log_index)
+    }
+
+    /// Deposit tree leaf the contract inserted for this deposit
+    pub fn leaf(&self) -> Result<[u8; 32], String> {
+        let commitment = crate::parse_bytes32(&self.commitment)
+            .ok_or_else(|| format!("Invalid commitment {}", self.commitment))?;
+        let amount_blinding = crate::parse_bytes32(&self.amount_blinding)
+            .ok_or_else(|| format!("Invalid amount blinding {}", self.amount_blinding))?;
+        Ok(compute_note_leaf(&commitment, self.asset_id, self.amount, &amount_blinding))
+    }
+}
+
+/// Raw log object as returned by `eth_getLogs`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcLog {
+    topics: Vec<String>,
+    #[serde(default)]
+    data: String,
+    block_number: String,
+    log_index: String,
+    transaction_hash: Option<String>,
+    #[serde(default)]
+    removed: bool,
+}
+
+fn parse_quantity(s: &str) -> Result<u64, String> {
+    u64::from_str_radix(s.trim_start_matches("0x"), 16).map_err(|e| format!("Invalid quantity {}: {}", s, e))
+}
+
+/// `(uint64 token, uint256 amount, bytes32 amountBlinding)` from the first three
+/// words of a deposit log's data
+fn parse_deposit_data(data: &str) -> Result<(u64, U256, [u8; 32]), String> {
+    let bytes = hex::decode(data.trim_start_matches("0x")).map_err(|e| format!("Invalid log data: {}", e))?;
+    if bytes.len() < 96 {
+        return Err(format!("Log data too short: {} bytes", bytes.len()));
+    }
+    if bytes[..24].iter().any(|b| *b != 0) {
+        return Err("Token does not fit in uint64".to_string());
+    }
+    let token = u64::from_be_bytes(bytes[24..32].try_into().unwrap());
+    let amount = U256::from_be_slice(&bytes[32..64]);
+    let amount_blinding: [u8; 32] = bytes[64..96].try_into().unwrap();
+    Ok((token, amount, amount_blinding))
+}
+
+/// Extract deposits from an `eth_getLogs` result, in chain order.
+/// Accepts a bare array of logs or a JSON-RPC response object with a `result` array.
+/// Removed (reorged) logs and unrelated events are skipped.
+pub fn parse_deposit_logs(logs: &Value) -> Result<Vec<DepositEvent>, String> {
+    let logs = logs.get("result").unwrap_or(logs);
+    let logs: Vec<RpcLog> =
+        serde_json::from_value(logs.clone()).map_err(|e| format!("Invalid log list: {}", e))?;
+
+    let topics: Vec<String> = PRIVATE_DEPOSIT_EVENTS
+        .iter()
+        .map(|sig| format!("0x{}", hex::encode(event_topic(sig))))
+        .collect();
+
+    let mut events = Vec::new();
+    for log in logs {
+        if log.removed || log.topics.is_empty() || !topics.contains(&log.topics[0].to_lowercase()) {
+            continue;
+        }
+        let commitment = log
+            .topics
+            .get(1)
+            .and_then(|t| crate::parse_bytes32(t))
+            .ok_or_else(|| format!("PrivateDeposit log without a commitment topic in block {}", log.block_number))?;
+        let (asset_id, amount, amount_blinding) = parse_deposit_data(&log.data)
+            .map_err(|e| format!("PrivateDeposit log in block {}: {}", log.block_number, e))?;
+
+        events.push(DepositEvent {
+            commitment: format!("0x{}", hex::encode(commitment)),
+            asset_id,
+            amount,
+            amount_blinding: format!("0x{}", hex::encode(amount_blinding)),
+            block_number: parse_quantity(&log.block_number)?,
+            log_index: parse_quantity(&log.log_index)?,
+            transaction_hash: log.transaction_hash,
+        });
+    }
+
+    events.sort_by_key(DepositEvent::cursor);
+    events.dedup_by_key(|e| e.cursor());
+    Ok(events)
+}
+
+/// Root published by `getMerkleRoot()` on the current contracts: the leaves
+/// folded into a keccak256 hash chain, zero when there are no deposits.
+pub fn chain_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    let mut iter = leaves.iter();
+    let Some(first) = iter.next() else {
+        return [0u8; 32];
+    };
+    iter.fold(*first, |root, leaf| {
+        let mut data = [0u8; 64];
+        data[..32].copy_from_slice(&root);
+        data[32..].copy_from_slice(leaf);
+        keccak256(&data)
+    })
+}
+
+/// Persisted deposit tree plus the sync cursor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositTree {
+    pub version: u32,
+    pub contract: Option<String>,
+    /// Last block fully scanned
+    pub synced_block: Option<u64>,
+    pub deposits: Vec<DepositEvent>,
+    pub tree: IncrementalMerkleTree,
+}
+
+impl DepositTree {
+    pub fn new(depth: usize, contract: Option<String>) -> Result<Self, String> {
+        Ok(Self {
+            version: STATE_VERSION,
+            contract,
+            synced_block: None,
+            deposits: Vec::new(),
+            tree: IncrementalMerkleTree::new(depth).map_err(|e| e.to_string())?,
+        })
+    }
+
+    /// Append deposits in chain order, skipping any at or before the last applied log.
+    /// Returns the number of new leaves.
+    pub fn apply(&mut self, events: &[DepositEvent]) -> Result<usize, String> {
+        let mut added = 0;
+        for event in events {
+            if let Some(last) = self.deposits.last() {
+                if event.cursor() <= last.cursor() {
+                    continue;
+                }
+            }
+            let leaf = event.leaf()?;
+            if self.deposit(&event.commitment).is_some() {
+                return Err(format!("Duplicate commitment {} in block {}", event.commitment, event.block_number));
+            }
+            self.tree.append(leaf).map_err(|e| e.to_string())?;
+            self.deposits.push(event.clone());
+            added += 1;
+        }
+        Ok(added)
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.tree.root()
+    }
+
+    /// The contract's `getMerkleRoot()` value for the indexed leaves
+    pub fn chain_root(&self) -> [u8; 32] {
+        chain_root(self.tree.leaves())
+    }
+
+    /// Check an on-chain root against the local tree root.
+    /// A match with the leaf hash chain is an error: paths from this tree do not
+    /// verify against it.
+    pub fn check_root(&self, onchain: &[u8; 32]) -> Result<(), String> {
+        if *onchain == self.root() {
+            Ok(())
+        } else if *onchain == self.chain_root() {
+            Err(format!(
+                "On-chain root 0x{} is the leaf hash chain over {} deposits; the contract's root format is \
+                 incompatible with tree paths (expected tree root 0x{})",
+                hex::encode(onchain),
+                self.deposits.len(),
+                hex::encode(self.root())
+            ))
+        } else {
+            Err(format!(
+                "Root mismatch: on-chain 0x{}, tree 0x{}, hash chain 0x{} ({} deposits indexed)",
+                hex::encode(onchain),
+                hex::encode(self.root()),
+                hex::encode(self.chain_root()),
+                self.deposits.len()
+            ))
+        }
+    }
+
+    /// Indexed deposit of a commitment
+    pub fn deposit(&self, commitment: &str) -> Option<&DepositEvent> {
+        self.deposits.iter().find(|d| d.commitment.eq_ignore_ascii_case(commitment))
+    }
+
+    /// Leaf index and Merkle path for a note leaf
+    pub fn path(&self, leaf: &[u8; 32]) -> Option<(u64, MerkleProof)> {
+        let index = self.tree.position(leaf)?;
+        let proof = self.tree.proof(index).ok()?;
+        Some((index, proof))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let data = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let state: Self = serde_json::from_str(&data)
+            .map_err(|e| format!("Invalid tree state {}: {}", path.display(), e))?;
+        if state.version != STATE_VERSION {
+            return Err(format!("Unsupported tree state version {}", state.version));
+        }
+        if state.deposits.len() as u64 != state.tree.len() {
+            return Err(format!("Tree state {} has {} deposits but {} leaves",
+                path.display(), state.deposits.len(), state.tree.len()));
+        }
+        Ok(state)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+/// Minimal blocking JSON-RPC client for the calls the indexer needs
+pub struct RpcClient {
+    url: String,
+    http: reqwest::blocking::Client,
+}
+
+impl RpcClient {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            http: reqwest::blocking::Client::new(),
+        }
+    }
+
+    pub fn call(&self, method: &str, params: Value) -> Result<Value, String> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Value = self
+            .http
+            .post(&self.url)
+            .json(&request)
+            .send()
+            .and_then(|r| r.json())
+            .map_err(|e| format!("{} request failed: {}", method, e))?;
+
+        if let Some(error) = response.get("error") {
+            return Err(format!("{} returned an error: {}", method, error));
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| format!("{} returned no result", method))
+    }
+
+    pub fn block_number(&self) -> Result<u64, String> {
+        let result = self.call("eth_blockNumber", json!([]))?;
+        parse_quantity(result.as_str().unwrap_or_default())
+    }
+
+    /// `PrivateDeposit` logs emitted by `contract` in `[from_block, to_block]`
+    pub fn deposit_logs(&self, contract: &str, from_block: u64, to_block: u64) -> Result<Value, String> {
+        let topics: Vec<String> = PRIVATE_DEPOSIT_EVENTS
+            .iter()
+            .map(|sig| format!("0x{}", hex::encode(event_topic(sig))))
+            .collect();
+        self.call(
+            "eth_getLogs",
+            json!([{
+                "address": contract,
+                "fromBlock": format!("0x{:x}", from_block),
+                "toBlock": format!("0x{:x}", to_block),
+                "topics": [topics],
+            }]),
+        )
+    }
+
+    /// `getMerkleRoot()` as of `block`
+    pub fn merkle_root(&self, contract: &str, block: u64) -> Result<[u8; 32], String> {
+        let selector = &keccak256(b"getMerkleRoot()")[..4];
+        let result = self.call(
+            "eth_call",
+            json!([{ "to": contract, "data": format!("0x{}", hex::encode(selector)) }, format!("0x{:x}", block)]),
+        )?;
+        result
+            .as_str()
+            .and_then(crate::parse_bytes32)
+            .ok_or_else(|| format!("Unexpected getMerkleRoot() result: {}", result))
+    }
+}
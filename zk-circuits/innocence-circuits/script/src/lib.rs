@@ -0,0 +1,25 @@
+//! Innocence Protocol - Prover Host Utilities
+//!
+//! Shared helpers for the proof generator binaries and operator tools.
+
+pub mod assets;
+pub mod compliance;
+pub mod credential;
+pub mod indexer;
+pub mod margin;
+pub mod notes;
+pub mod oracle;
+pub mod revocation;
+pub mod sanctions;
+
+/// Parse a 20-byte Ethereum address from a hex string (with or without 0x)
+pub fn parse_address(s: &str) -> Option<[u8; 20]> {
+    let bytes = hex::decode(s.trim().trim_start_matches("0x")).ok()?;
+    bytes.try_into().ok()
+}
+
+/// Parse a 32-byte value from a hex string (with or without 0x)
+pub fn parse_bytes32(s: &str) -> Option<[u8; 32]> {
+    let bytes = hex::decode(s.trim().trim_start_matches("0x")).ok()?;
+    bytes.try_into().ok()
+}
@@ -0,0 +1,122 @@
+//! Positions files
+//!
+//! A positions file describes a perpetuals margin account in asset units, the way
+//! a trader reads it:
+//!
+//! ```json
+//! {
+//!   "collateralAsset": "USDC",
+//!   "collateral": "5000",
+//!   "positions": [
+//!     { "asset": "UETH", "side": "long", "size": "2", "entryPrice": "3000" },
+//!     { "asset": "UBTC", "side": "short", "size": "0.1", "entryPrice": "60000" }
+//!   ],
+//!   "blinding": "0x..."
+//! }
+//! ```
+//!
+//! Prices are collateral units per unit of the position asset. The blinding
+//! factor fixes the account commitment; without one a random factor is drawn and
+//! the commitment changes on every run.
+//!
+//! The venue's accounts tree is read from a JSON array of account commitments in
+//! insertion order.
+
+use crate::oracle::parse_price;
+use innocence_circuits_lib::margin::{MarginAccount, Position, Side};
+use innocence_circuits_lib::{AssetRegistry, IncrementalMerkleTree, MarkPrice};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionsFile {
+    pub collateral_asset: String,
+    pub collateral: String,
+    #[serde(default)]
+    pub positions: Vec<PositionEntry>,
+    pub blinding: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionEntry {
+    pub asset: String,
+    pub side: Side,
+    pub size: String,
+    pub entry_price: String,
+}
+
+impl PositionsFile {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let data = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&data).map_err(|e| format!("Invalid positions file {}: {}", path.display(), e))
+    }
+
+    /// The margin account owned by `owner` (a public note key), in base units
+    pub fn to_account(&self, registry: &AssetRegistry, owner: &[u8; 32]) -> Result<MarginAccount, String> {
+        let collateral_asset = registry.resolve(&self.collateral_asset).map_err(|e| e.to_string())?;
+        let positions = self
+            .positions
+            .iter()
+            .map(|entry| {
+                let asset = registry.resolve(&entry.asset).map_err(|e| e.to_string())?;
+                Ok(Position {
+                    asset_id: asset.id.0,
+                    side: entry.side,
+                    size: asset
+                        .parse_amount(&entry.size)
+                        .map_err(|e| format!("Invalid {} size {}: {}", asset.symbol, entry.size, e))?,
+                    entry_price: parse_price(asset, collateral_asset, &entry.entry_price)?,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let blinding = match &self.blinding {
+            Some(b) => crate::parse_bytes32(b).ok_or("Invalid blinding hex")?,
+            None => rand::random(),
+        };
+        Ok(MarginAccount {
+            owner: *owner,
+            collateral_asset: collateral_asset.id.0,
+            collateral: collateral_asset
+                .parse_amount(&self.collateral)
+                .map_err(|e| format!("Invalid collateral {}: {}", self.collateral, e))?,
+            positions,
+            blinding,
+        })
+    }
+}
+
+/// Mark price for a command-line `ASSET=PRICE` in collateral units per asset unit
+pub fn parse_mark(
+    registry: &AssetRegistry,
+    collateral_asset: u64,
+    mark: &str,
+    maintenance_margin_bps: u32,
+) -> Result<MarkPrice, String> {
+    let (asset, price) = mark.split_once('=').ok_or_else(|| format!("Mark price {} is not ASSET=PRICE", mark))?;
+    let asset = registry.resolve(asset).map_err(|e| e.to_string())?;
+    let collateral = registry
+        .check(collateral_asset.into())
+        .map_err(|e| e.to_string())?;
+    Ok(MarkPrice {
+        assetId: asset.id.0,
+        price: parse_price(asset, collateral, price)?,
+        maintenanceMarginBps: maintenance_margin_bps,
+    })
+}
+
+/// Accounts tree of `depth` holding the commitments listed in an accounts file
+pub fn load_accounts_tree(path: &Path, depth: usize) -> Result<IncrementalMerkleTree, String> {
+    let data = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let commitments: Vec<String> = serde_json::from_str(&data)
+        .map_err(|e| format!("Invalid accounts file {}: {}", path.display(), e))?;
+    let leaves = commitments
+        .iter()
+        .map(|c| crate::parse_bytes32(c).ok_or_else(|| format!("Invalid account commitment {}", c)))
+        .collect::<Result<Vec<_>, String>>()?;
+    IncrementalMerkleTree::from_leaves(depth, &leaves).map_err(|e| e.to_string())
+}
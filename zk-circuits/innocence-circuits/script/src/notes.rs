@@ -0,0 +1,32 @@
+//! Note files
+//!
+//! Notes are the user's private state: losing a note file loses the funds in it.
+//! The host generates fresh randomness for every new note and stores notes as JSON.
+//! A note's `secret` is its owner's public note key, so notes can be created for a
+//! recipient who only shared that key.
+
+use innocence_circuits_lib::{Note, U256};
+use std::fs;
+use std::path::Path;
+
+/// A new note for the holder of `owner` (a public note key) with random blindings
+pub fn random_note(owner: &[u8; 32], asset_id: u64, amount: U256) -> Note {
+    Note {
+        asset_id,
+        amount,
+        secret: *owner,
+        blinding: rand::random(),
+        amount_blinding: rand::random(),
+    }
+}
+
+pub fn load_note(path: &Path) -> Result<Note, String> {
+    let data = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&data).map_err(|e| format!("Invalid note {}: {}", path.display(), e))
+}
+
+pub fn save_note(note: &Note, path: &Path) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(note).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
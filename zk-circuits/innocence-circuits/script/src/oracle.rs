@@ -0,0 +1,127 @@
+//! Price attestation files
+//!
+//! JSON form of the `SignedPrice` witness read by `trade-proof --attestation`, and
+//! signing for local test oracles (`sign-price`). Prices on the command line are
+//! decimal quote-asset units per base-asset unit, e.g. `0.00033` UETH per USDE.
+
+use innocence_circuits_lib::oracle::{attestation_digest, PriceAttestation, PRICE_DECIMALS};
+use innocence_circuits_lib::signature::public_key_to_address;
+use innocence_circuits_lib::{format_units, parse_units, Asset, SignedPrice, U256};
+use k256::ecdsa::SigningKey;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Attestation as stored on disk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttestationFile {
+    pub base_asset: u64,
+    pub quote_asset: u64,
+    /// Quote base units per base unit, scaled by 1e18, as a decimal integer
+    pub price: String,
+    pub timestamp: u64,
+    pub signer: String,
+    /// 65-byte r || s || v signature
+    pub signature: String,
+}
+
+impl From<&SignedPrice> for AttestationFile {
+    fn from(signed: &SignedPrice) -> Self {
+        let mut signature = Vec::with_capacity(65);
+        signature.extend_from_slice(&signed.r);
+        signature.extend_from_slice(&signed.s);
+        signature.push(signed.v);
+        Self {
+            base_asset: signed.base_asset,
+            quote_asset: signed.quote_asset,
+            price: signed.price.to_string(),
+            timestamp: signed.timestamp,
+            signer: format!("0x{}", hex::encode(signed.signer)),
+            signature: format!("0x{}", hex::encode(signature)),
+        }
+    }
+}
+
+impl TryFrom<&AttestationFile> for SignedPrice {
+    type Error = String;
+
+    fn try_from(file: &AttestationFile) -> Result<Self, Self::Error> {
+        let signature = hex::decode(file.signature.trim_start_matches("0x"))
+            .map_err(|e| format!("Invalid signature hex: {}", e))?;
+        if signature.len() != 65 {
+            return Err(format!("Signature must be 65 bytes, got {}", signature.len()));
+        }
+        Ok(SignedPrice {
+            base_asset: file.base_asset,
+            quote_asset: file.quote_asset,
+            price: file.price.parse().map_err(|e| format!("Invalid price {}: {}", file.price, e))?,
+            timestamp: file.timestamp,
+            signer: crate::parse_address(&file.signer).ok_or_else(|| format!("Invalid signer {}", file.signer))?,
+            r: signature[..32].try_into().unwrap(),
+            s: signature[32..64].try_into().unwrap(),
+            v: signature[64],
+        })
+    }
+}
+
+pub fn load_attestation(path: &Path) -> Result<SignedPrice, String> {
+    let data = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let file: AttestationFile = serde_json::from_str(&data)
+        .map_err(|e| format!("Invalid attestation {}: {}", path.display(), e))?;
+    SignedPrice::try_from(&file)
+}
+
+pub fn save_attestation(signed: &SignedPrice, path: &Path) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(&AttestationFile::from(signed)).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// secp256k1 signing key from hex
+pub fn parse_signing_key(s: &str) -> Result<SigningKey, String> {
+    let bytes = crate::parse_bytes32(s).ok_or("Signing key must be 32 bytes of hex")?;
+    SigningKey::from_slice(&bytes).map_err(|e| format!("Invalid signing key: {}", e))
+}
+
+/// Sign a price attestation as the oracle holding `key`
+pub fn sign_price(key: &SigningKey, base_asset: u64, quote_asset: u64, price: U256, timestamp: u64) -> SignedPrice {
+    let attestation = PriceAttestation {
+        baseAsset: base_asset,
+        quoteAsset: quote_asset,
+        price,
+        timestamp,
+    };
+    let (signature, recovery_id) = key
+        .sign_prehash_recoverable(&attestation_digest(&attestation))
+        .expect("Failed to sign price attestation");
+    let (r, s) = signature.split_bytes();
+    SignedPrice {
+        base_asset,
+        quote_asset,
+        price,
+        timestamp,
+        signer: public_key_to_address(key.verifying_key()),
+        v: 27 + recovery_id.to_byte(),
+        r: r.into(),
+        s: s.into(),
+    }
+}
+
+/// Fixed-point price for a decimal number of `quote` units per `base` unit
+pub fn parse_price(base: &Asset, quote: &Asset, price: &str) -> Result<U256, String> {
+    // price * 10^(18 + quote decimals) / 10^(base decimals), which must divide exactly
+    let scaled = parse_units(price, PRICE_DECIMALS + quote.decimals)
+        .map_err(|e| format!("Invalid price {}: {}", price, e))?;
+    let (raw, remainder) = scaled.div_rem(U256::from(10u64).pow(U256::from(base.decimals)));
+    if !remainder.is_zero() {
+        return Err(format!("Price {} is more precise than {} base units allow", price, quote.symbol));
+    }
+    Ok(raw)
+}
+
+/// Decimal `quote` units per `base` unit for a fixed-point price
+pub fn format_price(base: &Asset, quote: &Asset, price: U256) -> String {
+    let scaled = price.saturating_mul(U256::from(10u64).pow(U256::from(base.decimals)));
+    format_units(scaled, PRICE_DECIMALS + quote.decimals)
+}
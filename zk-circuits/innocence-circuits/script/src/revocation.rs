@@ -0,0 +1,94 @@
+//! Certificate revocation lists
+//!
+//! A compliance authority keeps the hashes of the certificates it has revoked in a
+//! versioned list file. The `revocationRoot` proofs are checked against is the
+//! root of the canonical `RevocationTree` over that list, so it can always be
+//! reproduced and witnessed from the file.
+
+use crate::parse_bytes32;
+use innocence_circuits_lib::RevocationTree;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Revocation list file format version
+pub const REVOCATION_LIST_VERSION: u32 = 1;
+
+/// Versioned revocation list, the source of truth for `revocationRoot`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevocationList {
+    pub version: u32,
+    pub root: String,
+    pub revoked_count: usize,
+    pub depth: usize,
+    pub updated_at: u64,
+    /// Revoked certificate hashes in ascending order
+    pub revoked: Vec<String>,
+}
+
+impl RevocationList {
+    pub fn new(hashes: &[[u8; 32]], updated_at: u64) -> Self {
+        let tree = RevocationTree::new(hashes);
+
+        let mut sorted = hashes.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        Self {
+            version: REVOCATION_LIST_VERSION,
+            root: format!("0x{}", hex::encode(tree.root())),
+            revoked_count: tree.len(),
+            depth: tree.depth(),
+            updated_at,
+            revoked: sorted.iter().map(|h| format!("0x{}", hex::encode(h))).collect(),
+        }
+    }
+
+    /// Revoked certificate hashes
+    pub fn hashes(&self) -> Result<Vec<[u8; 32]>, String> {
+        self.revoked
+            .iter()
+            .map(|h| parse_bytes32(h).ok_or_else(|| format!("Invalid certificate hash in list: {}", h)))
+            .collect()
+    }
+
+    /// Rebuild the tree and check it against the recorded root
+    pub fn tree(&self) -> Result<RevocationTree, String> {
+        if self.version != REVOCATION_LIST_VERSION {
+            return Err(format!("Unsupported revocation list version {}", self.version));
+        }
+
+        let tree = RevocationTree::new(&self.hashes()?);
+
+        let root = format!("0x{}", hex::encode(tree.root()));
+        if root != self.root {
+            return Err(format!("Revocation list root mismatch: recorded {}, rebuilt {}", self.root, root));
+        }
+        Ok(tree)
+    }
+
+    /// The list with `hashes` added
+    pub fn revoke(&self, hashes: &[[u8; 32]], updated_at: u64) -> Result<Self, String> {
+        let mut all = self.hashes()?;
+        all.extend_from_slice(hashes);
+        Ok(Self::new(&all, updated_at))
+    }
+
+    /// The list with `hashes` removed
+    pub fn reinstate(&self, hashes: &[[u8; 32]], updated_at: u64) -> Result<Self, String> {
+        let remaining: Vec<[u8; 32]> = self.hashes()?.into_iter().filter(|h| !hashes.contains(h)).collect();
+        Ok(Self::new(&remaining, updated_at))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let data = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&data).map_err(|e| format!("Invalid revocation list {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
@@ -0,0 +1,239 @@
+//! Sanctions list ingestion and snapshots
+//!
+//! Turns raw sanctions lists into the canonical `SanctionsTree` from
+//! `innocence-circuits-lib` and persists the result as a versioned snapshot,
+//! so the root posted on chain can always be reproduced and witnessed.
+
+use crate::parse_address;
+use innocence_circuits_lib::sanctions::{MAX_SENTINEL, MIN_SENTINEL};
+use innocence_circuits_lib::{AllowlistTree, SanctionsTree};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+/// Snapshot file format version
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Marker OFAC uses in the SDN remarks column for crypto addresses
+const OFAC_ADDRESS_MARKER: &str = "Digital Currency Address - ";
+
+/// Supported sanctions list formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ListFormat {
+    /// OFAC SDN CSV export, addresses taken from "Digital Currency Address - XXX" remarks
+    OfacCsv,
+    /// One address per line (first column if comma separated, '#' comments allowed)
+    List,
+    /// JSON array of addresses, or an object with an "addresses" array
+    Json,
+}
+
+impl ListFormat {
+    /// Guess the format from the file extension
+    pub fn detect(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => ListFormat::Json,
+            Some("csv") => ListFormat::OfacCsv,
+            _ => ListFormat::List,
+        }
+    }
+}
+
+/// Extract EVM addresses from an OFAC SDN CSV export.
+/// Addresses live in the free-text remarks column, so the whole export is scanned
+/// for the digital currency marker rather than parsed column by column.
+pub fn parse_ofac_csv(data: &str) -> Result<Vec<[u8; 20]>, String> {
+    let mut addresses = Vec::new();
+    for entry in data.split(OFAC_ADDRESS_MARKER).skip(1) {
+        // Entry looks like "ETH 0x8589...DA16; alt. Digital ..."
+        let address = entry
+            .split_whitespace()
+            .nth(1)
+            .map(|a| a.trim_end_matches([';', '.', ',', '"']));
+        // Non-EVM chains (XBT, XMR, TRX, ...) are skipped
+        if let Some(addr) = address.and_then(parse_address) {
+            addresses.push(addr);
+        }
+    }
+    if addresses.is_empty() {
+        return Err("No EVM digital currency addresses found".to_string());
+    }
+    Ok(addresses)
+}
+
+/// Parse a plain address list
+pub fn parse_plain_list(data: &str) -> Result<Vec<[u8; 20]>, String> {
+    let mut addresses = Vec::new();
+    for (line_no, line) in data.lines().enumerate() {
+        let entry = line.split(',').next().unwrap_or("").trim();
+        if entry.is_empty() || entry.starts_with('#') || entry.eq_ignore_ascii_case("address") {
+            continue;
+        }
+        let addr = parse_address(entry)
+            .ok_or_else(|| format!("Invalid address on line {}: {}", line_no + 1, entry))?;
+        addresses.push(addr);
+    }
+    Ok(addresses)
+}
+
+/// Parse a JSON address list
+pub fn parse_json_list(data: &str) -> Result<Vec<[u8; 20]>, String> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum JsonList {
+        Plain(Vec<String>),
+        Wrapped { addresses: Vec<String> },
+    }
+
+    let list: JsonList = serde_json::from_str(data).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let entries = match list {
+        JsonList::Plain(entries) | JsonList::Wrapped { addresses: entries } => entries,
+    };
+
+    entries
+        .iter()
+        .map(|a| parse_address(a).ok_or_else(|| format!("Invalid address: {}", a)))
+        .collect()
+}
+
+/// Parse raw list bytes in the given format
+pub fn parse_list(data: &[u8], format: ListFormat) -> Result<Vec<[u8; 20]>, String> {
+    let text = std::str::from_utf8(data).map_err(|e| format!("Invalid UTF-8: {}", e))?;
+    match format {
+        ListFormat::OfacCsv => parse_ofac_csv(text),
+        ListFormat::List => parse_plain_list(text),
+        ListFormat::Json => parse_json_list(text),
+    }
+}
+
+/// Reject the all-zero and all-0xff addresses. They are the tree's sentinel leaves,
+/// so listing one would silently merge it into a sentinel and drop it from the count.
+pub fn check_listable(addresses: &[[u8; 20]]) -> Result<(), String> {
+    match addresses.iter().find(|a| **a == MIN_SENTINEL || **a == MAX_SENTINEL) {
+        Some(a) => Err(format!("Reserved sentinel address 0x{} cannot be listed", hex::encode(a))),
+        None => Ok(()),
+    }
+}
+
+/// Provenance of one input list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotSource {
+    pub path: String,
+    pub format: ListFormat,
+    pub sha256: String,
+    pub address_count: usize,
+}
+
+/// Versioned sanctions (or allowlist) snapshot, the source of truth for `listRoot`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SanctionsSnapshot {
+    pub version: u32,
+    pub root: String,
+    pub leaf_count: usize,
+    pub depth: usize,
+    pub timestamp: u64,
+    pub sources: Vec<SnapshotSource>,
+    pub addresses: Vec<String>,
+}
+
+impl SanctionsSnapshot {
+    /// Build a snapshot from a set of list files
+    pub fn from_sources(
+        sources: &[(&Path, ListFormat)],
+        timestamp: u64,
+    ) -> Result<Self, String> {
+        let mut all = Vec::new();
+        let mut provenance = Vec::new();
+
+        for (path, format) in sources {
+            let data = fs::read(path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            let addresses = parse_list(&data, *format)
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+            provenance.push(SnapshotSource {
+                path: path.display().to_string(),
+                format: *format,
+                sha256: format!("0x{}", hex::encode(Sha256::digest(&data))),
+                address_count: addresses.len(),
+            });
+            all.extend(addresses);
+        }
+
+        Self::from_addresses(&all, provenance, timestamp)
+    }
+
+    pub fn from_addresses(
+        addresses: &[[u8; 20]],
+        sources: Vec<SnapshotSource>,
+        timestamp: u64,
+    ) -> Result<Self, String> {
+        check_listable(addresses)?;
+        let tree = SanctionsTree::new(addresses);
+
+        let mut sorted = addresses.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        Ok(Self {
+            version: SNAPSHOT_VERSION,
+            root: format!("0x{}", hex::encode(tree.root())),
+            leaf_count: tree.len(),
+            depth: tree.depth(),
+            timestamp,
+            sources,
+            addresses: sorted
+                .iter()
+                .map(|a| format!("0x{}", hex::encode(a)))
+                .collect(),
+        })
+    }
+
+    /// Rebuild the tree and check it against the recorded root
+    pub fn tree(&self) -> Result<SanctionsTree, String> {
+        if self.version != SNAPSHOT_VERSION {
+            return Err(format!("Unsupported snapshot version {}", self.version));
+        }
+
+        let addresses = self
+            .addresses
+            .iter()
+            .map(|a| parse_address(a).ok_or_else(|| format!("Invalid address in snapshot: {}", a)))
+            .collect::<Result<Vec<_>, _>>()?;
+        check_listable(&addresses)?;
+        let tree = SanctionsTree::new(&addresses);
+
+        let root = format!("0x{}", hex::encode(tree.root()));
+        if root != self.root {
+            return Err(format!("Snapshot root mismatch: recorded {}, rebuilt {}", self.root, root));
+        }
+        Ok(tree)
+    }
+
+    /// Check the snapshot and build the allowlist tree of its addresses. Allowlist
+    /// leaves are domain separated, so its root is not the recorded `root`
+    pub fn allowlist_tree(&self) -> Result<AllowlistTree, String> {
+        self.tree()?;
+        let addresses = self
+            .addresses
+            .iter()
+            .map(|a| parse_address(a).ok_or_else(|| format!("Invalid address in snapshot: {}", a)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(AllowlistTree::from_addresses(&addresses))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let data = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&data).map_err(|e| format!("Invalid snapshot {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
@@ -0,0 +1,59 @@
+//! Certificate files written by `issue-certificate` and read by the compliance
+//! prover.
+
+use innocence_circuits_lib::{AccreditationLevel, CertificateAttributes, CivilDate, Jurisdiction, KycTier};
+use innocence_prover::compliance::{issue_certificate, CertificateFile, SignedCertificate};
+use innocence_prover::oracle::parse_signing_key;
+
+const AUTHORITY_KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+fn attributes() -> CertificateAttributes {
+    CertificateAttributes {
+        jurisdiction: "CH".parse::<Jurisdiction>().unwrap(),
+        accreditation: AccreditationLevel::Accredited,
+        kyc_tier: KycTier::Enhanced,
+        date_of_birth: "1990-05-17".parse::<CivilDate>().unwrap(),
+    }
+}
+
+fn issue() -> SignedCertificate {
+    let key = parse_signing_key(AUTHORITY_KEY).unwrap();
+    issue_certificate(&key, [0x42; 32], 1_700_000_000, 1_731_536_000, attributes()).unwrap()
+}
+
+#[test]
+fn issued_certificate_round_trips() {
+    let signed = issue();
+    signed.verify().unwrap();
+    assert_eq!(signed.certificate.attributes, attributes());
+
+    let json = serde_json::to_string(&CertificateFile::from(&signed)).unwrap();
+    let file: CertificateFile = serde_json::from_str(&json).unwrap();
+    let loaded = SignedCertificate::try_from(&file).unwrap();
+    assert_eq!(loaded, signed);
+    loaded.verify().unwrap();
+}
+
+#[test]
+fn tampered_files_are_rejected() {
+    let signed = issue();
+    let file = CertificateFile::from(&signed);
+
+    // Readable fields must agree with the encoding
+    let mut wrong_issuer = file.clone();
+    wrong_issuer.issuer = format!("0x{}", "11".repeat(20));
+    assert!(SignedCertificate::try_from(&wrong_issuer).is_err());
+
+    let mut wrong_hash = file.clone();
+    wrong_hash.certificate_hash = format!("0x{}", "00".repeat(32));
+    assert!(SignedCertificate::try_from(&wrong_hash).is_err());
+
+    // A certificate re-signed by another key no longer verifies for its issuer
+    let other = parse_signing_key(&format!("0x{}", "07".repeat(32))).unwrap();
+    let forged = issue_certificate(&other, [0x42; 32], 1_700_000_000, 1_731_536_000, attributes()).unwrap();
+    let mut swapped = signed.clone();
+    swapped.r = forged.r;
+    swapped.s = forged.s;
+    swapped.v = forged.v;
+    assert!(swapped.verify().is_err());
+}
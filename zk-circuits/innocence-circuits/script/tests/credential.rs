@@ -0,0 +1,67 @@
+//! Credential-mode host checks against tokens signed with local keys.
+//!
+//! The fixtures in `fixtures/credentials` were signed with Node's crypto module.
+//! `issuer.jwk.json` is the issuer's public key; `vc.jwt` and `plain.jwt` are a
+//! VC-JWT and a plain JWT for the commitment of the default secret and
+//! nullifier; `other_issuer.jwt`, `expired.jwt` and `es256.jwt` are the same VC
+//! signed by another key, expired, and with an `ES256` header. `expected.json`
+//! holds the commitment and the RFC 7638 thumbprints of both keys.
+
+use innocence_circuits_lib::credential::jwk_thumbprint;
+use innocence_circuits_lib::{compute_commitment, AccreditationLevel, KycTier};
+use innocence_prover::credential::{load_credential, load_issuer_jwk, validate_credential};
+use innocence_prover::parse_bytes32;
+use serde_json::Value;
+use std::path::PathBuf;
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(format!("{}/tests/fixtures/credentials/{}", env!("CARGO_MANIFEST_DIR"), name))
+}
+
+fn expected() -> Value {
+    serde_json::from_str(&std::fs::read_to_string(fixture("expected.json")).unwrap()).unwrap()
+}
+
+fn bytes32(value: &Value) -> [u8; 32] {
+    parse_bytes32(value.as_str().unwrap()).unwrap()
+}
+
+#[test]
+fn validates_fixture_credentials() {
+    let expected = expected();
+    let commitment = bytes32(&expected["commitment"]);
+    assert_eq!(commitment, compute_commitment(&[1u8; 32], &[2u8; 32]));
+    let valid_at = expected["validAt"].as_u64().unwrap();
+
+    let issuer_key = load_issuer_jwk(&fixture("issuer.jwk.json")).unwrap();
+    assert_eq!(jwk_thumbprint(&issuer_key).unwrap(), bytes32(&expected["issuerKeyHash"]));
+
+    let vc = load_credential(&fixture("vc.jwt")).unwrap();
+    let credential = validate_credential(&vc, &issuer_key, &commitment, valid_at).unwrap();
+    assert_eq!(credential.claims.attributes.accreditation, AccreditationLevel::Accredited);
+    assert_eq!(credential.claims.attributes.kyc_tier, KycTier::Enhanced);
+
+    let plain = load_credential(&fixture("plain.jwt")).unwrap();
+    let credential = validate_credential(&plain, &issuer_key, &commitment, valid_at).unwrap();
+    assert_eq!(credential.claims.issuer.as_deref(), Some("https://kyc.example"));
+    assert_eq!(credential.claims.attributes.accreditation, AccreditationLevel::None);
+    assert_eq!(credential.claims.attributes.kyc_tier, KycTier::Basic);
+}
+
+#[test]
+fn rejects_invalid_fixture_credentials() {
+    let expected = expected();
+    let commitment = bytes32(&expected["commitment"]);
+    let valid_at = expected["validAt"].as_u64().unwrap();
+    let issuer_key = load_issuer_jwk(&fixture("issuer.jwk.json")).unwrap();
+    let validate = |name: &str, commitment: &[u8; 32], timestamp: u64| {
+        validate_credential(&load_credential(&fixture(name)).unwrap(), &issuer_key, commitment, timestamp)
+            .unwrap_err()
+    };
+
+    assert!(validate("other_issuer.jwt", &commitment, valid_at).contains("not signed by the issuer key"));
+    assert!(validate("expired.jwt", &commitment, valid_at).contains("not valid at"));
+    assert!(validate("es256.jwt", &commitment, valid_at).contains("unsupported JWS algorithm ES256"));
+    assert!(validate("vc.jwt", &[0u8; 32], valid_at).contains("is not the commitment"));
+    assert!(validate("vc.jwt", &commitment, 1_600_000_000).contains("not valid at"));
+}
@@ -0,0 +1,124 @@
+//! Sanctions list parsers and snapshots against fixture lists.
+//!
+//! `fixtures/sanctions/sdn.csv` follows the OFAC SDN CSV export, with EVM and
+//! non-EVM addresses in the remarks column; `list.txt`, `list.json` and
+//! `wrapped.json` are the plain and JSON list formats; `sentinel.txt` lists the
+//! zero address.
+
+use innocence_circuits_lib::SanctionsTree;
+use innocence_prover::parse_address;
+use innocence_prover::sanctions::{
+    check_listable, parse_json_list, parse_list, parse_ofac_csv, parse_plain_list, ListFormat,
+    SanctionsSnapshot,
+};
+use std::path::{Path, PathBuf};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(format!("{}/tests/fixtures/sanctions/{}", env!("CARGO_MANIFEST_DIR"), name))
+}
+
+fn read(name: &str) -> String {
+    std::fs::read_to_string(fixture(name)).unwrap()
+}
+
+fn addresses(list: &[&str]) -> Vec<[u8; 20]> {
+    list.iter().map(|a| parse_address(a).unwrap()).collect()
+}
+
+#[test]
+fn parses_ofac_csv_remarks() {
+    let parsed = parse_ofac_csv(&read("sdn.csv")).unwrap();
+    // XBT, XMR and TRX addresses are skipped; trailing ';', ',' and '.' are trimmed
+    assert_eq!(
+        parsed,
+        addresses(&[
+            "0x098B716B8Aaf21512996dC57EB0615e2383E2f96",
+            "0xa0e1c89Ef1a489c9C7dE96311eD5Ce5D32c20E4B",
+            "0x8589427373D6D84E98730D7795D8f6f8731FDA16",
+            "0x722122dF12D4e14e13Ac3b6895a86e84145b6967",
+        ])
+    );
+
+    assert!(parse_ofac_csv("1,\"NOBODY\",\"-0- \"").is_err());
+}
+
+#[test]
+fn parses_plain_lists() {
+    let parsed = parse_plain_list(&read("list.txt")).unwrap();
+    // Comments, the header, blank lines and labels are skipped; duplicates are kept
+    assert_eq!(
+        parsed,
+        addresses(&[
+            "0x8589427373D6D84E98730D7795D8f6f8731FDA16",
+            "0xDD4c48C0B24039969fC16D1cdF626eaB821d3384",
+            "0x8589427373D6D84E98730D7795D8f6f8731FDA16",
+        ])
+    );
+
+    let err = parse_plain_list("0x8589427373D6D84E98730D7795D8f6f8731FDA16\n0x1234\n").unwrap_err();
+    assert!(err.contains("line 2"), "{}", err);
+}
+
+#[test]
+fn parses_json_lists() {
+    assert_eq!(
+        parse_json_list(&read("list.json")).unwrap(),
+        addresses(&["0x722122dF12D4e14e13Ac3b6895a86e84145b6967", "0xDD4c48C0B24039969fC16D1cdF626eaB821d3384"])
+    );
+    assert_eq!(
+        parse_json_list(&read("wrapped.json")).unwrap(),
+        addresses(&["0x5Bd2F329C50860366c0E6D3b4227a422B66AD203", "0x098B716B8Aaf21512996dC57EB0615e2383E2f96"])
+    );
+    assert!(parse_json_list(r#"{"entries": []}"#).is_err());
+    assert!(parse_json_list(r#"["0xnothex"]"#).is_err());
+}
+
+#[test]
+fn detects_formats_from_extensions() {
+    assert_eq!(ListFormat::detect(Path::new("sdn.csv")), ListFormat::OfacCsv);
+    assert_eq!(ListFormat::detect(Path::new("list.json")), ListFormat::Json);
+    assert_eq!(ListFormat::detect(Path::new("list.txt")), ListFormat::List);
+    assert!(parse_list(&[0xff, 0xfe], ListFormat::List).is_err());
+}
+
+#[test]
+fn snapshot_merges_sources() {
+    let csv = fixture("sdn.csv");
+    let list = fixture("list.txt");
+    let json = fixture("list.json");
+    let snapshot = SanctionsSnapshot::from_sources(
+        &[(csv.as_path(), ListFormat::OfacCsv), (list.as_path(), ListFormat::List), (json.as_path(), ListFormat::Json)],
+        1_700_000_000,
+    )
+    .unwrap();
+
+    // 4 + 3 + 2 entries, 5 distinct addresses
+    let counts: Vec<usize> = snapshot.sources.iter().map(|s| s.address_count).collect();
+    assert_eq!(counts, [4, 3, 2]);
+    assert_eq!(snapshot.leaf_count, 5);
+    assert_eq!(snapshot.addresses.len(), 5);
+
+    let tree = snapshot.tree().unwrap();
+    assert_eq!(tree.len(), 5);
+    assert_eq!(format!("0x{}", hex::encode(tree.root())), snapshot.root);
+}
+
+#[test]
+fn rejects_sentinel_addresses() {
+    let listed = parse_plain_list(&read("sentinel.txt")).unwrap();
+    assert!(check_listable(&listed).is_err());
+    assert!(check_listable(&addresses(&["0xffffffffffffffffffffffffffffffffffffffff"])).is_err());
+    assert!(check_listable(&listed[..1]).is_ok());
+
+    let path = fixture("sentinel.txt");
+    let err = SanctionsSnapshot::from_sources(&[(path.as_path(), ListFormat::List)], 0).unwrap_err();
+    assert!(err.contains("sentinel"), "{}", err);
+
+    // Without the check the sentinel would merge into the bracket leaf and go uncounted
+    assert_eq!(SanctionsTree::new(&listed).len(), 1);
+
+    // A hand-edited snapshot listing a sentinel is rejected on load
+    let mut snapshot = SanctionsSnapshot::from_addresses(&listed[..1], Vec::new(), 0).unwrap();
+    snapshot.addresses.push(format!("0x{}", "00".repeat(20)));
+    assert!(snapshot.tree().is_err());
+}
@@ -1,65 +1,98 @@
 //! Trade Proof Circuit
 //! 
 //! This circuit proves that a user can execute a valid trade without revealing
-//! their actual balances. It ensures the user has sufficient balance for the trade
-//! and that the trade parameters are valid.
+//! their actual balances. It spends one input note from the deposit tree and
+//! creates two fresh notes: the change left in the `from` asset and the amount
+//! received in the `to` asset. The public values start with the
+//! `PrivateDEXExtension.SwapProofPublicValues` layout.
 //!
-//! Public inputs: commitment, fromAsset, toAsset, fromAmount, minToAmount
-//! Private inputs: secret, nullifier, fromBalance, toBalance
+//! Public inputs: merkleRoot, tokenIn, tokenOut, amountIn, minAmountOut, oracle, maxSlippageBps
+//! Private inputs: input_note, spending_key, merkle_proof, change_note, output_note, signed_price
 //! Proof: 
-//! 1. commitment = hash(secret || nullifier)
-//! 2. fromBalance >= fromAmount
-//! 3. Trade parameters are valid (non-zero amounts, different assets)
+//! 1. input_note.leaf() is in the deposit tree and the note is owned by
+//!    spending_key; commitment = input_note.commitment()
+//! 2. nullifierHash is derived from spending_key and the input note's position
+//! 3. input_note.amount = amountIn + change_note.amount, all in tokenIn
+//! 4. output_note holds minAmountOut of tokenOut
+//! 5. Trade parameters are valid (non-zero amounts, different assets)
+//! 6. signed_price is signed by oracle for tokenIn/tokenOut and
+//!    minAmountOut >= amountIn * price * (1 - maxSlippageBps / 10000)
 
 #![no_main]
 sp1_zkvm::entrypoint!(main);
 
 use alloy_sol_types::SolType;
-use innocence_circuits_lib::TradeProofPublicValues;
-use sha2::{Sha256, Digest};
+use innocence_circuits_lib::oracle::meets_min_output;
+use innocence_circuits_lib::{MerkleProof, Note, SignedPrice, SwapNoteProofPublicValues, U256};
 
 pub fn main() {
     // Read private inputs
-    let secret: [u8; 32] = sp1_zkvm::io::read();
-    let nullifier: [u8; 32] = sp1_zkvm::io::read();
-    let from_balance: u64 = sp1_zkvm::io::read();
-    let to_balance: u64 = sp1_zkvm::io::read();
+    let input_note: Note = sp1_zkvm::io::read();
+    let spending_key: [u8; 32] = sp1_zkvm::io::read();
+    let merkle_proof: MerkleProof = sp1_zkvm::io::read();
+    let change_note: Note = sp1_zkvm::io::read();
+    let output_note: Note = sp1_zkvm::io::read();
+    let signed_price: SignedPrice = sp1_zkvm::io::read();
     
     // Read public inputs
-    let expected_commitment: [u8; 32] = sp1_zkvm::io::read();
+    let merkle_root: [u8; 32] = sp1_zkvm::io::read();
     let from_asset: u64 = sp1_zkvm::io::read();
     let to_asset: u64 = sp1_zkvm::io::read();
-    let from_amount: u64 = sp1_zkvm::io::read();
-    let min_to_amount: u64 = sp1_zkvm::io::read();
+    let from_amount: U256 = sp1_zkvm::io::read();
+    let min_to_amount: U256 = sp1_zkvm::io::read();
+    let oracle: [u8; 20] = sp1_zkvm::io::read();
+    let max_slippage_bps: u32 = sp1_zkvm::io::read();
     
-    // Step 1: Verify commitment
-    let mut hasher = Sha256::new();
-    hasher.update(&secret);
-    hasher.update(&nullifier);
-    let computed_commitment: [u8; 32] = hasher.finalize().into();
+    // Step 1: Verify the input note is ours and in the deposit tree
+    assert!(
+        input_note.is_owned_by(&spending_key),
+        "Input note is not owned by the spending key"
+    );
+    let commitment = input_note.commitment();
+    assert_eq!(
+        merkle_proof.leaf,
+        input_note.leaf(),
+        "Merkle proof leaf does not match input note"
+    );
+    if let Err(e) = merkle_proof.verify(&merkle_root) {
+        panic!("Invalid merkle proof: {}", e);
+    }
     
+    // Step 2: Derive the nullifier hash of the spent note
+    let nullifier_hash = input_note.nullifier_hash(&spending_key, merkle_proof.leaf_index);
+    
+    // Step 3: Value conservation in the from asset
+    assert_eq!(input_note.asset_id, from_asset, "Input note asset mismatch");
+    assert_eq!(change_note.asset_id, from_asset, "Change note asset mismatch");
+    let spent = change_note
+        .amount
+        .checked_add(from_amount)
+        .expect("Change amount overflow");
     assert_eq!(
-        computed_commitment, 
-        expected_commitment, 
-        "Invalid commitment"
+        spent,
+        input_note.amount,
+        "Value not conserved: {} + {} != {}",
+        change_note.amount,
+        from_amount,
+        input_note.amount
     );
     
-    // Step 2: Verify sufficient balance for trade
-    assert!(
-        from_balance >= from_amount,
-        "Insufficient balance for trade: {} < {}",
-        from_balance,
-        from_amount
+    // Step 4: The received note holds the guaranteed swap output
+    assert_eq!(output_note.asset_id, to_asset, "Output note asset mismatch");
+    assert_eq!(
+        output_note.amount,
+        min_to_amount,
+        "Output note amount must equal min to amount"
     );
     
-    // Step 3: Verify trade parameters
+    // Step 5: Verify trade parameters
     assert!(
-        from_amount > 0,
+        from_amount > U256::ZERO,
         "From amount must be greater than zero"
     );
     
     assert!(
-        min_to_amount > 0,
+        min_to_amount > U256::ZERO,
         "Min to amount must be greater than zero"
     );
     
@@ -69,29 +102,41 @@ pub fn main() {
         "Cannot trade same asset"
     );
     
-    // Step 4: Additional validation
-    // Ensure no overflow in balance calculations
-    let new_from_balance = from_balance - from_amount;
+    // Step 6: The guaranteed output respects the oracle price and slippage bound
+    if let Err(e) = signed_price.verify(&oracle) {
+        panic!("Invalid price attestation: {}", e);
+    }
+    assert_eq!(signed_price.base_asset, from_asset, "Price attestation base asset mismatch");
+    assert_eq!(signed_price.quote_asset, to_asset, "Price attestation quote asset mismatch");
+    let within_slippage = meets_min_output(from_amount, min_to_amount, signed_price.price, max_slippage_bps)
+        .unwrap_or_else(|e| panic!("Invalid slippage check: {}", e));
     assert!(
-        new_from_balance <= from_balance,
-        "Balance underflow detected"
+        within_slippage,
+        "Min to amount {} is below the oracle price less {} bps slippage",
+        min_to_amount,
+        max_slippage_bps
     );
     
     // In a real implementation, we would also verify:
-    // - Price oracle data
-    // - Slippage tolerance
     // - MEV protection parameters
-    // - Cross-margin requirements for perps
+    // (perps margin health is proven separately by margin-proof)
     
     // Commit to public values
-    let public_values = TradeProofPublicValues {
-        commitment: expected_commitment.into(),
-        fromAsset: from_asset,
-        toAsset: to_asset,
-        fromAmount: alloy_sol_types::private::U256::from(from_amount),
-        minToAmount: alloy_sol_types::private::U256::from(min_to_amount),
+    let public_values = SwapNoteProofPublicValues {
+        commitment: commitment.into(),
+        nullifierHash: nullifier_hash.into(),
+        tokenIn: from_asset,
+        tokenOut: to_asset,
+        amountIn: from_amount,
+        minAmountOut: min_to_amount,
+        merkleRoot: merkle_root.into(),
+        changeLeaf: change_note.leaf().into(),
+        outputLeaf: output_note.leaf().into(),
+        oracle: oracle.into(),
+        priceTimestamp: signed_price.timestamp,
+        maxSlippageBps: max_slippage_bps,
     };
     
-    let bytes = TradeProofPublicValues::abi_encode(&public_values);
+    let bytes = SwapNoteProofPublicValues::abi_encode(&public_values);
     sp1_zkvm::io::commit_slice(&bytes);
-}
\ No newline at end of file
+}
@@ -0,0 +1,104 @@
+//! Transfer Proof Circuit
+//!
+//! This circuit proves a private 2-in/2-out join-split inside the pool: up to two
+//! notes are spent and two new notes are created, typically one for the recipient
+//! and one as change. An unused input slot holds a zero-amount dummy note, which
+//! needs no Merkle path or spending key.
+//!
+//! Public inputs: merkleRoot
+//! Private inputs: input notes, their merkle proofs, spending keys, output notes
+//! Proof:
+//! 1. Every non-zero input note is in the deposit tree and owned by its spending key
+//! 2. inputNullifierHash is derived from each input's spending key and position
+//! 3. For every asset, the inputs and outputs hold the same total amount
+//! 4. The two inputs have distinct nullifier hashes
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use alloy_sol_types::SolType;
+use innocence_circuits_lib::{MerkleProof, Note, TransferProofPublicValues, U256};
+
+/// Total amount of `asset_id` held by `notes`
+fn asset_total(notes: &[&Note], asset_id: u64) -> U256 {
+    notes
+        .iter()
+        .filter(|note| note.asset_id == asset_id)
+        .try_fold(U256::ZERO, |total, note| total.checked_add(note.amount))
+        .expect("Amount overflow")
+}
+
+pub fn main() {
+    // Read private inputs
+    let mut inputs: Vec<Note> = Vec::with_capacity(2);
+    let mut merkle_proofs: Vec<MerkleProof> = Vec::with_capacity(2);
+    let mut spending_keys: Vec<[u8; 32]> = Vec::with_capacity(2);
+    for _ in 0..2 {
+        inputs.push(sp1_zkvm::io::read());
+        merkle_proofs.push(sp1_zkvm::io::read());
+        spending_keys.push(sp1_zkvm::io::read());
+    }
+    let outputs: [Note; 2] = [sp1_zkvm::io::read(), sp1_zkvm::io::read()];
+    
+    // Read public inputs
+    let merkle_root: [u8; 32] = sp1_zkvm::io::read();
+    
+    // Step 1: Verify membership and ownership of the input notes
+    for (i, input) in inputs.iter().enumerate() {
+        if input.amount.is_zero() {
+            continue;
+        }
+        assert!(
+            input.is_owned_by(&spending_keys[i]),
+            "Input note {} is not owned by its spending key",
+            i + 1
+        );
+        assert_eq!(
+            merkle_proofs[i].leaf,
+            input.leaf(),
+            "Merkle proof leaf does not match input note {}",
+            i + 1
+        );
+        if let Err(e) = merkle_proofs[i].verify(&merkle_root) {
+            panic!("Invalid merkle proof for input note {}: {}", i + 1, e);
+        }
+    }
+    
+    // Step 2: Derive the nullifier hashes of the spent notes
+    let nullifier_hashes: Vec<[u8; 32]> = inputs
+        .iter()
+        .enumerate()
+        .map(|(i, input)| input.nullifier_hash(&spending_keys[i], merkle_proofs[i].leaf_index))
+        .collect();
+    
+    // Step 3: Value conservation per asset
+    let input_refs: Vec<&Note> = inputs.iter().collect();
+    let output_refs: Vec<&Note> = outputs.iter().collect();
+    for note in input_refs.iter().chain(output_refs.iter()) {
+        let total_in = asset_total(&input_refs, note.asset_id);
+        let total_out = asset_total(&output_refs, note.asset_id);
+        assert_eq!(
+            total_in,
+            total_out,
+            "Value not conserved for asset {}: {} in, {} out",
+            note.asset_id,
+            total_in,
+            total_out
+        );
+    }
+    
+    // Step 4: The same note cannot fill both input slots
+    assert_ne!(nullifier_hashes[0], nullifier_hashes[1], "Input notes share a nullifier hash");
+    
+    // Commit to public values
+    let public_values = TransferProofPublicValues {
+        merkleRoot: merkle_root.into(),
+        inputNullifierHash1: nullifier_hashes[0].into(),
+        inputNullifierHash2: nullifier_hashes[1].into(),
+        outputLeaf1: outputs[0].leaf().into(),
+        outputLeaf2: outputs[1].leaf().into(),
+    };
+    
+    let bytes = TransferProofPublicValues::abi_encode(&public_values);
+    sp1_zkvm::io::commit_slice(&bytes);
+}
@@ -0,0 +1,80 @@
+//! Withdraw Proof Circuit
+//! 
+//! This circuit proves that a user owns a deposited note and may withdraw it to
+//! a recipient, without revealing which deposit is being spent.
+//!
+//! Public inputs: merkle_root, recipient, relayer, fee, amount, asset_id
+//! Private inputs: spending_key, note, merkle_proof
+//! Proof: 
+//! 1. the note is owned by spending_key and holds `amount` of `asset_id`
+//! 2. leaf = compute_note_leaf(commitment, asset_id, amount, amount_blinding) is in merkle tree
+//! 3. nullifier_hash, derived from spending_key and the note's position, is exposed
+//!    so the note can only be spent once
+//! 4. fee <= amount
+//!
+//! recipient, relayer and fee are committed in the public values, so a proof
+//! taken from the mempool cannot be replayed to a different destination.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use alloy_sol_types::SolType;
+use innocence_circuits_lib::{
+    note::Note, MerkleProof, WithdrawProofPublicValues, U256,
+};
+
+pub fn main() {
+    // Read private inputs
+    let spending_key: [u8; 32] = sp1_zkvm::io::read();
+    let note: Note = sp1_zkvm::io::read();
+    let merkle_proof: MerkleProof = sp1_zkvm::io::read();
+    
+    // Read public inputs
+    let merkle_root: [u8; 32] = sp1_zkvm::io::read();
+    let recipient: [u8; 20] = sp1_zkvm::io::read();
+    let relayer: [u8; 20] = sp1_zkvm::io::read();
+    let fee: U256 = sp1_zkvm::io::read();
+    let amount: U256 = sp1_zkvm::io::read();
+    let asset_id: u64 = sp1_zkvm::io::read();
+    
+    // Step 1: The whole note is withdrawn by its owner
+    assert!(note.is_owned_by(&spending_key), "Note is not owned by the spending key");
+    assert_eq!(note.asset_id, asset_id, "Note asset mismatch");
+    assert_eq!(note.amount, amount, "Note amount mismatch");
+    
+    // Step 2: Verify the note is in the deposit tree
+    let leaf = note.leaf();
+    assert_eq!(
+        merkle_proof.leaf,
+        leaf,
+        "Merkle proof leaf does not match computed leaf"
+    );
+    if let Err(e) = merkle_proof.verify(&merkle_root) {
+        panic!("Invalid merkle proof: {}", e);
+    }
+    
+    // Step 3: Derive the nullifier hash
+    let nullifier_hash = note.nullifier_hash(&spending_key, merkle_proof.leaf_index);
+    
+    // Step 4: The relayer fee is paid out of the withdrawn amount
+    assert!(
+        fee <= amount,
+        "Fee exceeds withdrawal amount: {} > {}",
+        fee,
+        amount
+    );
+    
+    // Commit to public values
+    let public_values = WithdrawProofPublicValues {
+        merkleRoot: merkle_root.into(),
+        nullifierHash: nullifier_hash.into(),
+        recipient: recipient.into(),
+        relayer: relayer.into(),
+        fee,
+        amount,
+        assetId: asset_id,
+    };
+    
+    let bytes = WithdrawProofPublicValues::abi_encode(&public_values);
+    sp1_zkvm::io::commit_slice(&bytes);
+}